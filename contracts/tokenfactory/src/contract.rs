@@ -9,8 +9,9 @@ use crate::error::TokenFactoryError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use crate::state::{Config, CONFIG, DENOM_OWNER};
 use token_bindings::{
-    DenomsByCreatorResponse, FullDenomResponse, Metadata, MetadataResponse, ParamsResponse,
-    TokenFactoryMsg, TokenFactoryMsgOptions, TokenFactoryQuery, TokenQuerier,
+    DenomError, DenomsByCreatorResponse, FactoryDenom, FullDenomResponse, Metadata,
+    MetadataResponse, ParamsResponse, TokenFactoryMsg, TokenFactoryMsgOptions, TokenFactoryQuery,
+    TokenQuerier,
 };
 
 // version info for migration info
@@ -56,6 +57,7 @@ pub fn execute(
             denom,
             new_admin_address,
         } => change_admin(deps, info, denom, new_admin_address),
+        ExecuteMsg::RenounceAdmin { denom } => renounce_admin(deps, info, denom),
         ExecuteMsg::MintTokens {
             denom,
             amount,
@@ -108,18 +110,23 @@ pub fn create_denom(
         return Err(TokenFactoryError::InvalidFund {});
     }
 
-    if subdenom.eq("") {
-        return Err(TokenFactoryError::InvalidSubdenom { subdenom });
+    let full_denom =
+        FactoryDenom::new(env.contract.address.clone(), subdenom.clone()).map_err(|err| {
+            TokenFactoryError::InvalidDenom {
+                denom: format!("factory/{}/{}", env.contract.address, subdenom),
+                message: err.to_string(),
+            }
+        })?;
+    if let Some(metadata) = &metadata {
+        metadata.validate(&full_denom.to_string())?;
     }
+    DENOM_OWNER.save(deps.storage, full_denom.to_string(), &info.sender)?;
 
     let create_denom_msg = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
-        subdenom: subdenom.clone(),
+        subdenom: full_denom.subdenom().to_string(),
         metadata,
     });
 
-    let full_denom = format!("factory/{}/{}", env.contract.address, subdenom);
-    DENOM_OWNER.save(deps.storage, full_denom, &info.sender)?;
-
     let res = Response::new()
         .add_attribute("method", "create_denom")
         .add_message(create_denom_msg);
@@ -170,6 +177,24 @@ pub fn change_admin(
     Ok(res)
 }
 
+pub fn renounce_admin(
+    mut deps: DepsMut<TokenFactoryQuery>,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    validate_denom_owner(deps.as_ref(), denom.clone(), info.sender)?;
+    validate_denom(deps.branch(), denom.clone())?;
+
+    // the contract is no longer admin, so nobody may act through it either
+    DENOM_OWNER.remove(deps.storage, denom.clone());
+
+    let res = Response::new()
+        .add_attribute("method", "renounce_admin")
+        .add_message(TokenFactoryMsg::renounce_admin(denom));
+
+    Ok(res)
+}
+
 pub fn mint_tokens(
     deps: DepsMut<TokenFactoryQuery>,
     info: MessageInfo,
@@ -249,9 +274,11 @@ pub fn query(deps: Deps<TokenFactoryQuery>, _env: Env, msg: QueryMsg) -> StdResu
             creator_address,
             subdenom,
         } => to_json_binary(&get_denom(deps, creator_address, subdenom)?),
-        QueryMsg::DenomsByCreator { creator } => {
-            to_json_binary(&get_denoms_by_creator(deps, creator)?)
-        }
+        QueryMsg::DenomsByCreator {
+            creator,
+            start_after,
+            limit,
+        } => to_json_binary(&get_denoms_by_creator(deps, creator, start_after, limit)?),
         QueryMsg::GetMetadata { denom } => to_json_binary(&get_metadata(deps, denom)?),
         QueryMsg::GetParams {} => to_json_binary(&get_params(deps)?),
     }
@@ -271,9 +298,11 @@ fn get_denom(
 fn get_denoms_by_creator(
     deps: Deps<TokenFactoryQuery>,
     creator_addr: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
 ) -> StdResult<DenomsByCreatorResponse> {
     let querier = TokenQuerier::new(&deps.querier);
-    let response = querier.denom_by_creator(creator_addr)?;
+    let response = querier.denoms_by_creator_page(creator_addr, start_after, limit)?;
     Ok(response)
 }
 
@@ -292,42 +321,28 @@ fn get_params(deps: Deps<TokenFactoryQuery>) -> StdResult<ParamsResponse> {
 fn validate_denom(
     deps: DepsMut<TokenFactoryQuery>,
     denom: String,
-) -> Result<(), TokenFactoryError> {
-    let denom_to_split = denom.clone();
-    let tokenfactory_denom_parts: Vec<&str> = denom_to_split.split('/').collect();
-
-    if tokenfactory_denom_parts.len() != 3 {
-        return Result::Err(TokenFactoryError::InvalidDenom {
-            denom,
-            message: std::format!(
-                "denom must have 3 parts separated by /, had {}",
-                tokenfactory_denom_parts.len()
-            ),
-        });
-    }
-
-    let prefix = tokenfactory_denom_parts[0];
-    let creator_address = tokenfactory_denom_parts[1];
-    let subdenom = tokenfactory_denom_parts[2];
-
-    if !prefix.eq_ignore_ascii_case("factory") {
-        return Result::Err(TokenFactoryError::InvalidDenom {
-            denom,
-            message: std::format!("prefix must be 'factory', was {}", prefix),
-        });
-    }
+) -> Result<FactoryDenom, TokenFactoryError> {
+    let factory_denom: FactoryDenom =
+        denom
+            .parse()
+            .map_err(|err: DenomError| TokenFactoryError::InvalidDenom {
+                denom: denom.clone(),
+                message: err.to_string(),
+            })?;
 
     // Validate denom by attempting to query for full denom
-    let response = TokenQuerier::new(&deps.querier)
-        .full_denom(String::from(creator_address), String::from(subdenom));
-    if response.is_err() {
-        return Result::Err(TokenFactoryError::InvalidDenom {
+    let response = TokenQuerier::new(&deps.querier).full_denom(
+        factory_denom.creator().to_string(),
+        factory_denom.subdenom().to_string(),
+    );
+    if let Err(err) = response {
+        return Err(TokenFactoryError::InvalidDenom {
             denom,
-            message: response.err().unwrap().to_string(),
+            message: err.to_string(),
         });
     }
 
-    Result::Ok(())
+    Ok(factory_denom)
 }
 
 fn validate_denom_owner(
@@ -353,7 +368,9 @@ mod tests {
     };
 
     use std::marker::PhantomData;
-    use token_bindings::{FullDenomResponse, TokenFactoryQuery, TokenFactoryQueryEnum};
+    use token_bindings::{
+        FullDenomResponse, MetadataError, TokenFactoryQuery, TokenFactoryQueryEnum,
+    };
     use token_bindings_test::TokenFactoryApp;
 
     const DENOM_NAME: &str = "mydenom";
@@ -380,13 +397,13 @@ mod tests {
                 }) => {
                     let binary_request = to_json_binary(a).unwrap();
 
-                    if creator_addr.eq("") {
+                    if creator_addr.is_empty() {
                         return SystemResult::Err(SystemError::InvalidRequest {
                             error: String::from("invalid creator address"),
                             request: binary_request,
                         });
                     }
-                    if subdenom.eq("") {
+                    if subdenom.is_empty() {
                         return SystemResult::Err(SystemError::InvalidRequest {
                             error: String::from("invalid subdenom"),
                             request: binary_request,
@@ -473,13 +490,13 @@ mod tests {
                 metadata: None,
             },
         ));
-        let actual_message = res.messages.get(0).unwrap();
+        let actual_message = res.messages.first().unwrap();
         assert_eq!(expected_message, actual_message.msg);
 
         assert_eq!(1, res.attributes.len());
 
         let expected_attribute = Attribute::new("method", "create_denom");
-        let actual_attribute = res.attributes.get(0).unwrap();
+        let actual_attribute = res.attributes.first().unwrap();
         assert_eq!(expected_attribute, actual_attribute);
 
         assert_eq!(res.data.ok_or(0), Err(0));
@@ -507,6 +524,60 @@ mod tests {
         assert_eq!(TokenFactoryError::InvalidFund {}, err);
     }
 
+    #[test]
+    fn msg_create_denom_invalid_subdenom_character() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = ExecuteMsg::CreateDenom {
+            subdenom: String::from("my denom"),
+            metadata: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        let expected_error = TokenFactoryError::InvalidDenom {
+            denom: format!("{}/{}/my denom", DENOM_PREFIX, MOCK_CONTRACT_ADDR),
+            message: String::from(
+                "subdenom 'my denom' may only contain characters in [a-zA-Z0-9/:._-]",
+            ),
+        };
+        assert_eq!(expected_error, err);
+    }
+
+    #[test]
+    fn msg_create_denom_invalid_metadata() {
+        let mut deps = mock_dependencies();
+
+        let full_denom_name = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        let metadata = Metadata {
+            symbol: None,
+            ..Metadata::builder(&full_denom_name)
+                .display("MYDENOM", 6)
+                .build()
+                .unwrap()
+        };
+
+        let info = mock_info("creator", &[]);
+        let msg = ExecuteMsg::CreateDenom {
+            subdenom: String::from(DENOM_NAME),
+            metadata: Some(metadata.clone()),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            TokenFactoryError::InvalidMetadata(MetadataError::BlankSymbol)
+        );
+        assert!(!DENOM_OWNER.has(deps.as_ref().storage, full_denom_name));
+
+        let msg = ExecuteMsg::CreateDenom {
+            subdenom: String::from(DENOM_NAME),
+            metadata: Some(Metadata {
+                symbol: Some("MYDENOM".to_string()),
+                ..metadata
+            }),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
     #[test]
     fn msg_change_admin_success() {
         let mut deps = mock_dependencies();
@@ -552,13 +623,13 @@ mod tests {
                 new_admin_address: String::from(NEW_ADMIN_ADDR),
             },
         ));
-        let actual_message = res.messages.get(0).unwrap();
+        let actual_message = res.messages.first().unwrap();
         assert_eq!(expected_message, actual_message.msg);
 
         assert_eq!(1, res.attributes.len());
 
         let expected_attribute = Attribute::new("method", "change_admin");
-        let actual_attribute = res.attributes.get(0).unwrap();
+        let actual_attribute = res.attributes.first().unwrap();
         assert_eq!(expected_attribute, actual_attribute);
 
         assert_eq!(res.data.ok_or(0), Err(0));
@@ -585,6 +656,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn msg_renounce_admin_success() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &coins(2, "token"));
+
+        let full_denom_name: &str =
+            &format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME)[..];
+
+        let msg = ExecuteMsg::RenounceAdmin {
+            denom: String::from(full_denom_name),
+        };
+
+        // case 1: unauthorized
+        DENOM_OWNER
+            .save(
+                deps.as_mut().storage,
+                full_denom_name.to_string(),
+                &Addr::unchecked("sender"),
+            )
+            .unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        assert_eq!(err, TokenFactoryError::Unauthorized {});
+
+        // case 2: success
+        DENOM_OWNER
+            .save(
+                deps.as_mut().storage,
+                full_denom_name.to_string(),
+                &Addr::unchecked("creator"),
+            )
+            .unwrap();
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+
+        assert_eq!(1, res.messages.len());
+        let expected_message = CosmosMsg::from(TokenFactoryMsg::Token(
+            TokenFactoryMsgOptions::ChangeAdmin {
+                denom: String::from(full_denom_name),
+                new_admin_address: String::new(),
+            },
+        ));
+        assert_eq!(expected_message, res.messages.first().unwrap().msg);
+        assert_eq!(res.attributes, vec![attr("method", "renounce_admin")]);
+
+        // case 3: the creator no longer owns the denom
+        assert!(!DENOM_OWNER.has(deps.as_ref().storage, full_denom_name.to_string()));
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    }
+
     #[test]
     fn msg_validate_denom_too_many_parts_valid() {
         let mut deps = mock_dependencies();
@@ -593,7 +713,9 @@ mod tests {
         let full_denom_name: &str =
             &format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME)[..];
 
-        validate_denom(deps.as_mut(), String::from(full_denom_name)).unwrap()
+        let denom = validate_denom(deps.as_mut(), String::from(full_denom_name)).unwrap();
+        assert_eq!(denom.creator().as_str(), MOCK_CONTRACT_ADDR);
+        assert_eq!(denom.subdenom(), DENOM_NAME);
     }
 
     #[test]
@@ -604,11 +726,9 @@ mod tests {
 
         let info = mock_info("creator", &coins(2, "token"));
 
-        // too many parts in denom
-        let full_denom_name: &str = &format!(
-            "{}/{}/{}/invalid",
-            DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME
-        )[..];
+        // prefix is case sensitive
+        let full_denom_name: &str =
+            &format!("{}/{}/{}", "FACTORY", MOCK_CONTRACT_ADDR, DENOM_NAME)[..];
         DENOM_OWNER
             .save(
                 deps.as_mut().storage,
@@ -625,7 +745,7 @@ mod tests {
 
         let expected_error = TokenFactoryError::InvalidDenom {
             denom: String::from(full_denom_name),
-            message: String::from("denom must have 3 parts separated by /, had 4"),
+            message: String::from("prefix must be 'factory', was FACTORY"),
         };
 
         assert_eq!(expected_error, err);
@@ -667,13 +787,13 @@ mod tests {
                 amount: mint_amount,
                 mint_to_address: String::from(NEW_ADMIN_ADDR),
             }));
-        let actual_message = res.messages.get(0).unwrap();
+        let actual_message = res.messages.first().unwrap();
         assert_eq!(expected_message, actual_message.msg);
 
         assert_eq!(1, res.attributes.len());
 
         let expected_attribute = Attribute::new("method", "mint_tokens");
-        let actual_attribute = res.attributes.get(0).unwrap();
+        let actual_attribute = res.attributes.first().unwrap();
         assert_eq!(expected_attribute, actual_attribute);
 
         assert_eq!(res.data.ok_or(0), Err(0));
@@ -736,7 +856,7 @@ mod tests {
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
         let expected_error = TokenFactoryError::InvalidDenom {
             denom: String::from(full_denom_name),
-            message: String::from("denom must have 3 parts separated by /, had 2"),
+            message: String::from("denom must have at least 3 parts separated by /, had 2"),
         };
 
         assert_eq!(expected_error, err);
@@ -785,13 +905,13 @@ mod tests {
                 amount: mint_amount,
                 burn_from_address: String::from(""),
             }));
-        let actual_message = res.messages.get(0).unwrap();
+        let actual_message = res.messages.first().unwrap();
         assert_eq!(expected_message, actual_message.msg);
 
         assert_eq!(1, res.attributes.len());
 
         let expected_attribute = Attribute::new("method", "burn_tokens");
-        let actual_attribute = res.attributes.get(0).unwrap();
+        let actual_attribute = res.attributes.first().unwrap();
         assert_eq!(expected_attribute, actual_attribute);
 
         assert_eq!(res.data.ok_or(0), Err(0))
@@ -868,20 +988,34 @@ mod tests {
     }
 
     #[test]
-    fn msg_validate_denom_too_many_parts_invalid() {
+    fn msg_validate_denom_nested_subdenom_valid() {
         let mut deps = mock_dependencies();
 
-        // too many parts in denom
+        // subdenoms may contain '/'
         let full_denom_name: &str = &format!(
-            "{}/{}/{}/invalid",
+            "{}/{}/{}/nested",
             DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME
         )[..];
 
+        let denom = validate_denom(deps.as_mut(), String::from(full_denom_name)).unwrap();
+        assert_eq!(denom.subdenom(), format!("{}/nested", DENOM_NAME));
+    }
+
+    #[test]
+    fn msg_validate_denom_subdenom_invalid() {
+        let mut deps = mock_dependencies();
+
+        // space is not allowed in subdenom
+        let full_denom_name: &str =
+            &format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, "my denom")[..];
+
         let err = validate_denom(deps.as_mut(), String::from(full_denom_name)).unwrap_err();
 
         let expected_error = TokenFactoryError::InvalidDenom {
             denom: String::from(full_denom_name),
-            message: String::from("denom must have 3 parts separated by /, had 4"),
+            message: String::from(
+                "subdenom 'my denom' may only contain characters in [a-zA-Z0-9/:._-]",
+            ),
         };
 
         assert_eq!(expected_error, err);
@@ -898,7 +1032,7 @@ mod tests {
 
         let expected_error = TokenFactoryError::InvalidDenom {
             denom: String::from(full_denom_name),
-            message: String::from("denom must have 3 parts separated by /, had 2"),
+            message: String::from("denom must have at least 3 parts separated by /, had 2"),
         };
 
         assert_eq!(expected_error, err);
@@ -1,5 +1,6 @@
 use cosmwasm_std::StdError;
 use thiserror::Error;
+use token_bindings::MetadataError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum TokenFactoryError {
@@ -9,12 +10,12 @@ pub enum TokenFactoryError {
     #[error("Unauthorized")]
     Unauthorized {},
 
-    #[error("Invalid subdenom: {subdenom:?}")]
-    InvalidSubdenom { subdenom: String },
-
     #[error("Invalid denom: {denom:?} {message:?}")]
     InvalidDenom { denom: String, message: String },
 
+    #[error("Invalid metadata: {0}")]
+    InvalidMetadata(#[from] MetadataError),
+
     #[error("denom does not exist: {denom:?}")]
     DenomDoesNotExist { denom: String },
 
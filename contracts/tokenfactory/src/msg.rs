@@ -22,6 +22,11 @@ pub enum ExecuteMsg {
         denom: String,
         new_admin_address: String,
     },
+    /// Leaves the denom without an admin, after which nobody can mint,
+    /// burn or change it. This cannot be undone.
+    RenounceAdmin {
+        denom: String,
+    },
     MintTokens {
         denom: String,
         amount: Uint128,
@@ -51,7 +56,11 @@ pub enum QueryMsg {
     #[returns(token_bindings::MetadataResponse)]
     GetMetadata { denom: String },
     #[returns(token_bindings::DenomsByCreatorResponse)]
-    DenomsByCreator { creator: String },
+    DenomsByCreator {
+        creator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     #[returns(token_bindings::ParamsResponse)]
     GetParams {},
 }
@@ -0,0 +1,237 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{
+    to_json_binary, Addr, Api, BankMsg, BankQuery, Binary, BlockInfo, Coin, CustomMsg, CustomQuery,
+    Empty, Order, Querier, StdResult, Storage,
+};
+use cw_multi_test::{AppResponse, Bank, BankKeeper, BankSudo, CosmosRouter, Module, WasmSudo};
+use cw_storage_plus::Map;
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "cosmwasm_1_3")]
+use cosmwasm_std::{DenomMetadata, DenomUnit};
+use token_bindings::BeforeSendHookSudoMsg;
+#[cfg(feature = "cosmwasm_1_3")]
+use token_bindings::Metadata;
+
+use crate::multitest::{decrease_supply, increase_supply, BEFORE_SEND_HOOK};
+
+// every account that was ever credited, as the `BankKeeper` cannot list its accounts
+const ACCOUNTS: Map<&Addr, Empty> = Map::new("bank_accounts");
+
+/// Address of the token factory module account.
+/// Hooks see it as sender of minted and recipient of burned tokens.
+pub const TOKEN_FACTORY_MODULE_ADDR: &str = "tokenfactory";
+
+/// A bank module that calls the before send hooks registered via
+/// `SetBeforeSendHook` before moving any hooked denom, like the
+/// token factory hooks into the bank keeper on chain.
+/// Everything else is delegated to the plain `BankKeeper`.
+#[derive(Default)]
+pub struct TokenFactoryBank {
+    bank: BankKeeper,
+}
+
+impl TokenFactoryBank {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every account that may have a balance, i.e. all that were ever credited
+    pub fn accounts(&self, storage: &dyn Storage) -> StdResult<Vec<Addr>> {
+        ACCOUNTS
+            .keys(storage, None, None, Order::Ascending)
+            .collect()
+    }
+
+    /// Administration function for adjusting bank accounts in genesis.
+    pub fn init_balance(
+        &self,
+        storage: &mut dyn Storage,
+        account: &Addr,
+        amount: Vec<Coin>,
+    ) -> AnyResult<()> {
+        ACCOUNTS.save(storage, account, &Empty {})?;
+        self.bank.init_balance(storage, account, amount)
+    }
+}
+
+/// Mirrors the metadata of a factory denom into the bank module,
+/// so `BankQuery::DenomMetadata` and `AllDenomMetadata` return it like on chain.
+/// Unset fields are empty, as in the bank module's protobuf types.
+#[cfg(feature = "cosmwasm_1_3")]
+pub(crate) fn set_denom_metadata(
+    storage: &mut dyn Storage,
+    denom: &str,
+    metadata: &Metadata,
+) -> AnyResult<()> {
+    let metadata = metadata.clone();
+    let denom_units = metadata
+        .denom_units
+        .into_iter()
+        .map(|unit| DenomUnit {
+            denom: unit.denom,
+            exponent: unit.exponent,
+            aliases: unit.aliases,
+        })
+        .collect();
+    let metadata = DenomMetadata {
+        description: metadata.description.unwrap_or_default(),
+        denom_units,
+        base: metadata.base.unwrap_or_default(),
+        display: metadata.display.unwrap_or_default(),
+        name: metadata.name.unwrap_or_default(),
+        symbol: metadata.symbol.unwrap_or_default(),
+        uri: metadata.uri.unwrap_or_default(),
+        uri_hash: metadata.uri_hash.unwrap_or_default(),
+    };
+    // the bank keeper keeps denom metadata outside of its prefixed storage
+    BankKeeper::new().set_denom_metadata(storage, denom.to_string(), metadata)
+}
+
+/// Calls BlockBeforeSend and then TrackBeforeSend on the hook of every
+/// hooked denom in amount. Errors of BlockBeforeSend abort the transfer,
+/// errors of TrackBeforeSend are ignored.
+fn call_before_send_hooks<ExecC, QueryC>(
+    api: &dyn Api,
+    storage: &mut dyn Storage,
+    router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+    block: &BlockInfo,
+    from: &str,
+    to: &str,
+    amount: &[Coin],
+) -> AnyResult<()>
+where
+    ExecC: CustomMsg + DeserializeOwned + 'static,
+    QueryC: CustomQuery + DeserializeOwned + 'static,
+{
+    for coin in amount {
+        let contract_addr = match BEFORE_SEND_HOOK.may_load(storage, &coin.denom)? {
+            Some(contract_addr) => contract_addr,
+            None => continue,
+        };
+
+        let block_msg = BeforeSendHookSudoMsg::BlockBeforeSend {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: coin.clone(),
+        };
+        let sudo = WasmSudo {
+            contract_addr: contract_addr.clone(),
+            message: to_json_binary(&block_msg)?,
+        };
+        router.sudo(api, storage, block, sudo.into())?;
+
+        let track_msg = BeforeSendHookSudoMsg::TrackBeforeSend {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: coin.clone(),
+        };
+        let sudo = WasmSudo {
+            contract_addr,
+            message: to_json_binary(&track_msg)?,
+        };
+        // the contract's state changes are reverted on error, but the transfer goes on
+        let _ = router.sudo(api, storage, block, sudo.into());
+    }
+    Ok(())
+}
+
+impl Module for TokenFactoryBank {
+    type ExecT = BankMsg;
+    type QueryT = BankQuery;
+    type SudoT = BankSudo;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: Self::ExecT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match &msg {
+            BankMsg::Send { to_address, amount } => call_before_send_hooks(
+                api,
+                storage,
+                router,
+                block,
+                sender.as_str(),
+                to_address,
+                amount,
+            )?,
+            BankMsg::Burn { amount } => call_before_send_hooks(
+                api,
+                storage,
+                router,
+                block,
+                sender.as_str(),
+                TOKEN_FACTORY_MODULE_ADDR,
+                amount,
+            )?,
+            _ => {}
+        }
+        let res = self
+            .bank
+            .execute(api, storage, router, block, sender, msg.clone())?;
+        match &msg {
+            BankMsg::Send { to_address, .. } => {
+                ACCOUNTS.save(storage, &Addr::unchecked(to_address), &Empty {})?
+            }
+            // burns of factory tokens via BankMsg::Burn count as well, like on chain
+            BankMsg::Burn { amount } => decrease_supply(storage, amount)?,
+            _ => {}
+        }
+        Ok(res)
+    }
+
+    fn query(
+        &self,
+        api: &dyn Api,
+        storage: &dyn Storage,
+        querier: &dyn Querier,
+        block: &BlockInfo,
+        request: Self::QueryT,
+    ) -> AnyResult<Binary> {
+        self.bank.query(api, storage, querier, block, request)
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        msg: Self::SudoT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match &msg {
+            BankSudo::Mint { to_address, amount } => call_before_send_hooks(
+                api,
+                storage,
+                router,
+                block,
+                TOKEN_FACTORY_MODULE_ADDR,
+                to_address,
+                amount,
+            )?,
+        }
+        let res = self.bank.sudo(api, storage, router, block, msg.clone())?;
+        match &msg {
+            BankSudo::Mint { to_address, amount } => {
+                ACCOUNTS.save(storage, &Addr::unchecked(to_address), &Empty {})?;
+                increase_supply(storage, amount)?
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl Bank for TokenFactoryBank {}
@@ -0,0 +1,320 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{Addr, BlockInfo, Coin};
+use cw_multi_test::BasicAppBuilder;
+
+use token_bindings::stargate::OSMOSIS_TOKENFACTORY_PACKAGE;
+use token_bindings::{Metadata, Params, TokenFactoryMsg, TokenFactoryQuery};
+
+use crate::bank::TokenFactoryBank;
+use crate::genesis::TokenFactoryGenesis;
+use crate::multitest::{TokenFactoryApp, TokenFactoryModule, DEFAULT_FEE_COLLECTOR};
+use crate::stargate::TokenFactoryStargate;
+
+/// A denom that exists from genesis on
+struct BuilderDenom {
+    creator: Addr,
+    subdenom: String,
+    admin: Addr,
+    metadata: Option<Metadata>,
+}
+
+/// Builds a `TokenFactoryApp` with the given params and genesis state.
+///
+/// ```
+/// use cosmwasm_std::{coins, Addr};
+/// use token_bindings_test::TokenFactoryAppBuilder;
+///
+/// let app = TokenFactoryAppBuilder::new()
+///     .with_denom_creation_fee(coins(1000, "uosmo"))
+///     .with_balance(Addr::unchecked("creator"), coins(5000, "uosmo"))
+///     .with_denom(Addr::unchecked("creator"), "fundz", Addr::unchecked("creator"), None)
+///     .build();
+/// ```
+pub struct TokenFactoryAppBuilder {
+    api: MockApi,
+    block: Option<BlockInfo>,
+    params: Params,
+    fee_collector: Addr,
+    balances: BTreeMap<Addr, Vec<Coin>>,
+    denoms: Vec<BuilderDenom>,
+    genesis: Option<TokenFactoryGenesis>,
+    stargate_package: String,
+    check_invariants: bool,
+}
+
+impl Default for TokenFactoryAppBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenFactoryAppBuilder {
+    pub fn new() -> Self {
+        TokenFactoryAppBuilder {
+            api: MockApi::default(),
+            block: None,
+            params: Params::default(),
+            fee_collector: Addr::unchecked(DEFAULT_FEE_COLLECTOR),
+            balances: BTreeMap::new(),
+            denoms: vec![],
+            genesis: None,
+            stargate_package: OSMOSIS_TOKENFACTORY_PACKAGE.to_string(),
+            check_invariants: false,
+        }
+    }
+
+    /// Uses this api, e.g. `MockApi::default().with_prefix("osmo")` for bech32 addresses
+    pub fn with_api(mut self, api: MockApi) -> Self {
+        self.api = api;
+        self
+    }
+
+    /// Starts the chain at this block instead of the `mock_env` one
+    pub fn with_block(mut self, block: BlockInfo) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn with_params(mut self, params: Params) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn with_denom_creation_fee(mut self, denom_creation_fee: Vec<Coin>) -> Self {
+        self.params.denom_creation_fee = denom_creation_fee;
+        self
+    }
+
+    pub fn with_denom_creation_gas_consume(mut self, denom_creation_gas_consume: u64) -> Self {
+        self.params.denom_creation_gas_consume = denom_creation_gas_consume;
+        self
+    }
+
+    /// Sends the denom creation fees to this address instead of `DEFAULT_FEE_COLLECTOR`
+    pub fn with_fee_collector(mut self, fee_collector: Addr) -> Self {
+        self.fee_collector = fee_collector;
+        self
+    }
+
+    /// Sets the initial balance of `account`, replacing the one of an earlier call.
+    /// Balances of genesis denoms count towards their total supply.
+    pub fn with_balance(mut self, account: Addr, amount: Vec<Coin>) -> Self {
+        self.balances.insert(account, amount);
+        self
+    }
+
+    /// Creates factory/{creator}/{subdenom} in genesis, administered by `admin`
+    pub fn with_denom(
+        mut self,
+        creator: Addr,
+        subdenom: impl Into<String>,
+        admin: Addr,
+        metadata: Option<Metadata>,
+    ) -> Self {
+        self.denoms.push(BuilderDenom {
+            creator,
+            subdenom: subdenom.into(),
+            admin,
+            metadata,
+        });
+        self
+    }
+
+    /// Loads the state of a chain's genesis, e.g. to reproduce a production state.
+    /// Denoms of `with_denom` are added to it.
+    pub fn with_genesis(mut self, genesis: TokenFactoryGenesis) -> Self {
+        self.genesis = Some(genesis);
+        self
+    }
+
+    /// Handles stargate messages of this protobuf package instead of the Osmosis one
+    pub fn with_stargate_package(mut self, package: impl Into<String>) -> Self {
+        self.stargate_package = package.into();
+        self
+    }
+
+    /// Asserts the invariants of the token factory after every execution, see
+    /// `TokenFactoryApp::assert_token_factory_invariants`. Slow, but catches
+    /// inconsistencies as soon as they happen.
+    pub fn with_invariant_checks(mut self, check_invariants: bool) -> Self {
+        self.check_invariants = check_invariants;
+        self
+    }
+
+    /// Builds the app, panicking if the genesis state is invalid
+    pub fn build(self) -> TokenFactoryApp {
+        let TokenFactoryAppBuilder {
+            api,
+            block,
+            params,
+            fee_collector,
+            balances,
+            denoms,
+            genesis,
+            stargate_package,
+            check_invariants,
+        } = self;
+
+        let module = TokenFactoryModule::with_params(params).with_fee_collector(fee_collector);
+        let builder = BasicAppBuilder::<TokenFactoryMsg, TokenFactoryQuery>::new_custom()
+            .with_api(api)
+            .with_bank(TokenFactoryBank::new())
+            .with_stargate(TokenFactoryStargate::with_package(
+                module.clone(),
+                stargate_package,
+            ))
+            .with_custom(module);
+        let builder = match block {
+            Some(block) => builder.with_block(block),
+            None => builder,
+        };
+
+        let app = builder.build(|router, _, storage| {
+            if let Some(genesis) = genesis {
+                router
+                    .custom
+                    .import_genesis(storage, genesis)
+                    .expect("invalid genesis");
+            }
+            for denom in denoms {
+                router
+                    .custom
+                    .init_denom(
+                        storage,
+                        &denom.creator,
+                        &denom.subdenom,
+                        &denom.admin,
+                        denom.metadata,
+                    )
+                    .expect("invalid genesis denom");
+            }
+            for (account, amount) in balances {
+                router
+                    .custom
+                    .init_supply(storage, &amount)
+                    .expect("invalid genesis supply");
+                router
+                    .bank
+                    .init_balance(storage, &account, amount)
+                    .expect("invalid genesis balance");
+            }
+        });
+        let app = TokenFactoryApp {
+            app,
+            check_invariants,
+        };
+        app.check_invariants();
+        app
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coin, coins, Api, Timestamp, Uint128};
+    use cw_multi_test::Executor;
+    use token_bindings::{TokenFactoryMsgOptions, TokenQuerier};
+
+    #[test]
+    fn builds_genesis_state() {
+        let api = MockApi::default().with_prefix("osmo");
+        let creator = api.addr_make("creator");
+        let admin = api.addr_make("admin");
+        let holder = api.addr_make("holder");
+        let block = BlockInfo {
+            height: 1234,
+            time: Timestamp::from_seconds(1_700_000_000),
+            chain_id: "osmosis-1".to_string(),
+        };
+        let denom = format!("factory/{}/gen", creator);
+        let metadata = Metadata::builder(&denom)
+            .description("Genesis token")
+            .name("Genesis")
+            .symbol("GEN")
+            .build()
+            .unwrap();
+
+        let mut app = TokenFactoryAppBuilder::new()
+            .with_api(api)
+            .with_block(block.clone())
+            .with_params(Params {
+                denom_creation_fee: coins(100, "uosmo"),
+                denom_creation_gas_consume: 0,
+            })
+            .with_denom(
+                creator.clone(),
+                "gen",
+                admin.clone(),
+                Some(metadata.clone()),
+            )
+            .with_balance(creator.clone(), coins(150, "uosmo"))
+            .with_balance(holder.clone(), vec![coin(5, "uosmo"), coin(77, &denom)])
+            .build();
+
+        assert_eq!(app.block_info(), block);
+        assert!(app.api().addr_validate(creator.as_str()).is_ok());
+
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert_eq!(
+            querier.params().unwrap().params.denom_creation_fee,
+            coins(100, "uosmo")
+        );
+        assert_eq!(querier.admin(&denom).unwrap().admin, admin.to_string());
+        assert_eq!(querier.metadata(&denom).unwrap().metadata, Some(metadata));
+        assert_eq!(
+            querier
+                .denom_by_creator(creator.to_string())
+                .unwrap()
+                .denoms,
+            vec![denom.clone()]
+        );
+        // only factory denoms are tracked
+        assert_eq!(
+            querier.total_supply(&denom).unwrap().amount,
+            coin(77, &denom)
+        );
+        assert_eq!(
+            querier.total_supply("uosmo").unwrap().amount.amount,
+            Uint128::zero()
+        );
+        assert_eq!(
+            app.wrap().query_balance(&holder, &denom).unwrap(),
+            coin(77, &denom)
+        );
+
+        // the genesis admin can mint, the creation fee is charged
+        let mint =
+            TokenFactoryMsg::mint_contract_tokens(&denom, Uint128::new(3), holder.to_string());
+        app.execute(admin, mint.into()).unwrap();
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "other".to_string(),
+            metadata: None,
+        });
+        app.execute(creator.clone(), create.into()).unwrap();
+        assert_eq!(
+            app.wrap().query_balance(&creator, "uosmo").unwrap(),
+            coin(50, "uosmo")
+        );
+    }
+
+    #[test]
+    fn defaults_match_new() {
+        let app = TokenFactoryAppBuilder::new().build();
+        assert_eq!(app.block_info(), mock_env().block);
+        assert_eq!(app.fee_collector(), Addr::unchecked(DEFAULT_FEE_COLLECTOR));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid genesis denom")]
+    fn duplicate_genesis_denom() {
+        let creator = Addr::unchecked("creator");
+        TokenFactoryAppBuilder::new()
+            .with_denom(creator.clone(), "gen", creator.clone(), None)
+            .with_denom(creator.clone(), "gen", creator, None)
+            .build();
+    }
+}
@@ -1,20 +1,35 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
 use thiserror::Error;
+use token_bindings::{DenomError, MetadataError};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
-    #[error("Invalid full denom '{full_denom}'")]
-    InvalidFullDenom { full_denom: String },
+    #[error("Invalid full denom: {0}")]
+    InvalidFullDenom(#[from] DenomError),
 
     #[error("Not admin of token, cannot perfrom action")]
     NotTokenAdmin,
 
+    #[error("Invalid metadata: {0}")]
+    InvalidMetadata(#[from] MetadataError),
+
+    #[error("Invalid uri hash '{uri_hash}', must be a hex encoded SHA-256 hash")]
+    InvalidUriHash { uri_hash: String },
+
     #[error("Token denom already exists, cannot create again")]
     TokenExists,
 
     #[error("Token denom was never created")]
     TokenDoesntExist,
+
+    #[error("Denom creation is disabled")]
+    CreationDisabled,
+
+    #[error(
+        "Insufficient funds for denom creation fee: required {required}, available {available}"
+    )]
+    InsufficientCreationFee { required: Coin, available: Coin },
 }
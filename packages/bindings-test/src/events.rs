@@ -0,0 +1,24 @@
+//! Event types and attribute keys emitted by `TokenFactoryModule`.
+//! These are the same as in the chain module (x/tokenfactory/types/events.go),
+//! so contracts and indexers can be tested against them.
+
+pub const EVENT_CREATE_DENOM: &str = "create_denom";
+pub const EVENT_MINT: &str = "tf_mint";
+pub const EVENT_BURN: &str = "tf_burn";
+pub const EVENT_CHANGE_ADMIN: &str = "change_admin";
+pub const EVENT_SET_DENOM_METADATA: &str = "set_denom_metadata";
+pub const EVENT_FORCE_TRANSFER: &str = "force_transfer";
+pub const EVENT_SET_BEFORE_SEND_HOOK: &str = "set_before_send_hook";
+
+pub const ATTR_AMOUNT: &str = "amount";
+pub const ATTR_CREATOR: &str = "creator";
+pub const ATTR_NEW_TOKEN_DENOM: &str = "new_token_denom";
+pub const ATTR_MINT_TO_ADDRESS: &str = "mint_to_address";
+pub const ATTR_BURN_FROM_ADDRESS: &str = "burn_from_address";
+pub const ATTR_TRANSFER_FROM_ADDRESS: &str = "transfer_from_address";
+pub const ATTR_TRANSFER_TO_ADDRESS: &str = "transfer_to_address";
+pub const ATTR_DENOM: &str = "denom";
+pub const ATTR_NEW_ADMIN: &str = "new_admin";
+/// The chain uses the protobuf text format here, the mock uses JSON
+pub const ATTR_DENOM_METADATA: &str = "denom_metadata";
+pub const ATTR_BEFORE_SEND_HOOK_ADDRESS: &str = "before_send_hook_address";
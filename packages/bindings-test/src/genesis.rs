@@ -0,0 +1,285 @@
+//! Genesis state of the token factory, in the JSON layout of the chain module's
+//! `app_state.tokenfactory`, so a snippet of a real genesis can be loaded into
+//! `TokenFactoryApp` and compared with an exported state.
+//!
+//! ```
+//! use cosmwasm_std::from_json;
+//! use token_bindings_test::{TokenFactoryAppBuilder, TokenFactoryGenesis};
+//!
+//! let genesis: TokenFactoryGenesis = from_json(
+//!     br#"{
+//!         "params": {
+//!             "denom_creation_fee": [{ "denom": "uosmo", "amount": "10000000" }],
+//!             "denom_creation_gas_consume": "2000000"
+//!         },
+//!         "factory_denoms": [{
+//!             "denom": "factory/osmo1creator/fundz",
+//!             "authority_metadata": { "admin": "osmo1creator" }
+//!         }]
+//!     }"#,
+//! )
+//! .unwrap();
+//! let app = TokenFactoryAppBuilder::new().with_genesis(genesis).build();
+//! ```
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use token_bindings::Metadata;
+
+use crate::stargate::{StargateDenomAuthorityMetadata, StargateParams};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct TokenFactoryGenesis {
+    /// The module's params, or the ones it was configured with if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<StargateParams>,
+    #[serde(default)]
+    pub factory_denoms: Vec<GenesisDenom>,
+    /// Not part of the token factory's genesis on chain, but of the bank module's.
+    /// Entries for other than the factory denoms are ignored, so the bank module's
+    /// `denom_metadata` can be copied as is.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denom_metadata: Vec<Metadata>,
+}
+
+/// A factory denom and its admin, which is empty if it was renounced
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GenesisDenom {
+    pub denom: String,
+    pub authority_metadata: StargateDenomAuthorityMetadata,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{coins, from_json, Addr, Uint128, Uint64};
+    use cw_multi_test::Executor;
+    use token_bindings::{TokenFactoryMsg, TokenFactoryMsgOptions, TokenQuerier};
+
+    use crate::error::ContractError;
+    use crate::{TokenFactoryApp, TokenFactoryAppBuilder};
+
+    const CHAIN_GENESIS: &[u8] = br#"{
+        "params": {
+            "denom_creation_fee": [{ "denom": "uosmo", "amount": "10000000" }],
+            "denom_creation_gas_consume": "2000000"
+        },
+        "factory_denoms": [
+            {
+                "denom": "factory/osmo1creator/fundz",
+                "authority_metadata": { "admin": "osmo1admin" }
+            },
+            {
+                "denom": "factory/osmo1creator/renounced",
+                "authority_metadata": { "admin": "" }
+            }
+        ],
+        "denom_metadata": [
+            {
+                "description": "",
+                "denom_units": [
+                    { "denom": "factory/osmo1creator/fundz", "exponent": 0, "aliases": [] },
+                    { "denom": "FUNDZ", "exponent": 6, "aliases": [] }
+                ],
+                "base": "factory/osmo1creator/fundz",
+                "display": "FUNDZ",
+                "name": "Fundz",
+                "symbol": "FUNDZ",
+                "uri": "",
+                "uri_hash": ""
+            },
+            {
+                "description": "The native token",
+                "denom_units": [{ "denom": "uosmo", "exponent": 0, "aliases": [] }],
+                "base": "uosmo",
+                "display": "uosmo",
+                "name": "Osmosis",
+                "symbol": "OSMO",
+                "uri": "",
+                "uri_hash": ""
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn imports_chain_genesis() {
+        let genesis: TokenFactoryGenesis = from_json(CHAIN_GENESIS).unwrap();
+        let mut app = TokenFactoryAppBuilder::new()
+            .with_genesis(genesis)
+            .with_balance(
+                Addr::unchecked("holder"),
+                coins(77, "factory/osmo1creator/fundz"),
+            )
+            .build();
+
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        let params = querier.params().unwrap().params;
+        assert_eq!(params.denom_creation_fee, coins(10_000_000, "uosmo"));
+        assert_eq!(params.denom_creation_gas_consume, 2_000_000);
+        assert_eq!(
+            querier.admin("factory/osmo1creator/fundz").unwrap().admin,
+            "osmo1admin"
+        );
+        assert_eq!(
+            querier
+                .admin("factory/osmo1creator/renounced")
+                .unwrap()
+                .admin,
+            ""
+        );
+        assert_eq!(
+            querier
+                .denom_by_creator("osmo1creator".to_string())
+                .unwrap()
+                .denoms
+                .len(),
+            2
+        );
+        let metadata = querier
+            .metadata("factory/osmo1creator/fundz")
+            .unwrap()
+            .metadata
+            .unwrap();
+        assert_eq!(metadata.display.as_deref(), Some("FUNDZ"));
+        assert_eq!(
+            querier.metadata("uosmo").unwrap().metadata,
+            None,
+            "only factory denoms are imported"
+        );
+        assert_eq!(
+            querier
+                .total_supply("factory/osmo1creator/fundz")
+                .unwrap()
+                .amount
+                .amount,
+            Uint128::new(77)
+        );
+
+        // the imported admin is in charge
+        let mint = TokenFactoryMsg::mint_contract_tokens(
+            "factory/osmo1creator/fundz",
+            Uint128::new(1),
+            "holder".to_string(),
+        );
+        app.execute(Addr::unchecked("osmo1admin"), mint.clone().into())
+            .unwrap();
+        let err = app
+            .execute(Addr::unchecked("osmo1creator"), mint.into())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotTokenAdmin
+        );
+    }
+
+    #[test]
+    fn export_import_round_trip() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryAppBuilder::new()
+            .with_denom_creation_gas_consume(1000)
+            .build();
+        let metadata = Metadata::builder("factory/creator/fundz")
+            .display("FUNDZ", 6)
+            .build()
+            .unwrap();
+        for (subdenom, metadata) in [("fundz", Some(metadata)), ("plain", None)] {
+            let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+                subdenom: subdenom.to_string(),
+                metadata,
+            });
+            app.execute(creator.clone(), create.into()).unwrap();
+        }
+        let renounce = TokenFactoryMsg::renounce_admin("factory/creator/plain");
+        app.execute(creator, renounce.into()).unwrap();
+
+        let genesis = app.export_genesis();
+        assert_eq!(
+            genesis.params.as_ref().unwrap().denom_creation_gas_consume,
+            Uint64::new(1000)
+        );
+        assert_eq!(genesis.factory_denoms.len(), 2);
+        assert_eq!(genesis.factory_denoms[1].authority_metadata.admin, "");
+        assert_eq!(genesis.denom_metadata.len(), 1);
+
+        let mut imported = TokenFactoryApp::new();
+        imported.import_genesis(genesis.clone()).unwrap();
+        assert_eq!(imported.export_genesis(), genesis);
+
+        // the denoms exist now
+        let err = imported.import_genesis(genesis).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::TokenExists
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_denoms() {
+        let genesis = TokenFactoryGenesis {
+            factory_denoms: vec![GenesisDenom {
+                denom: "ibc/osmo1creator/fundz".to_string(),
+                authority_metadata: StargateDenomAuthorityMetadata {
+                    admin: "osmo1admin".to_string(),
+                },
+            }],
+            ..TokenFactoryGenesis::default()
+        };
+        let err = TokenFactoryApp::new().import_genesis(genesis).unwrap_err();
+        assert!(matches!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidFullDenom(_)
+        ));
+    }
+
+    #[test]
+    fn failed_import_changes_nothing() {
+        let mut app = TokenFactoryAppBuilder::new()
+            .with_denom(
+                Addr::unchecked("creator"),
+                "fundz",
+                Addr::unchecked("creator"),
+                None,
+            )
+            .build();
+        let before = app.export_genesis();
+
+        let denom = |denom: &str| GenesisDenom {
+            denom: denom.to_string(),
+            authority_metadata: StargateDenomAuthorityMetadata {
+                admin: "osmo1admin".to_string(),
+            },
+        };
+        let params = Some(StargateParams {
+            denom_creation_fee: coins(10_000_000, "uosmo"),
+            denom_creation_gas_consume: Uint64::new(2_000_000),
+        });
+        let invalid_genesis = [
+            // an existing denom
+            vec![
+                denom("factory/osmo1creator/new"),
+                denom("factory/creator/fundz"),
+            ],
+            // the same denom twice
+            vec![
+                denom("factory/osmo1creator/new"),
+                denom("factory/osmo1creator/new"),
+            ],
+            // an invalid denom
+            vec![
+                denom("factory/osmo1creator/new"),
+                denom("factory/osmo1creator/a b"),
+            ],
+        ];
+        for factory_denoms in invalid_genesis {
+            let genesis = TokenFactoryGenesis {
+                params: params.clone(),
+                factory_denoms,
+                ..TokenFactoryGenesis::default()
+            };
+            app.import_genesis(genesis).unwrap_err();
+            assert_eq!(app.export_genesis(), before);
+        }
+    }
+}
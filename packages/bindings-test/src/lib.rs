@@ -1,6 +1,16 @@
+mod bank;
+mod builder;
 pub mod error;
+pub mod events;
+mod genesis;
 mod multitest;
+pub mod stargate;
 
+pub use bank::{TokenFactoryBank, TOKEN_FACTORY_MODULE_ADDR};
+pub use builder::TokenFactoryAppBuilder;
+pub use genesis::{GenesisDenom, TokenFactoryGenesis};
 pub use multitest::{
     TokenFactoryApp, TokenFactoryAppWrapped, TokenFactoryError, TokenFactoryModule,
+    TokenFactorySudo, DEFAULT_DENOMS_LIMIT, DEFAULT_FEE_COLLECTOR, MAX_DENOMS_LIMIT,
 };
+pub use stargate::TokenFactoryStargate;
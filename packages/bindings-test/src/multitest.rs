@@ -1,73 +1,385 @@
-use anyhow::{bail, Result as AnyResult};
+use anyhow::Result as AnyResult;
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::cmp::max;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
 use thiserror::Error;
 
 use cosmwasm_std::testing::{MockApi, MockStorage};
 use cosmwasm_std::{
-    coins, to_json_binary, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomMsg, CustomQuery,
-    Empty, Querier, QuerierResult, StdError, Storage,
+    coin, coins, from_json, to_json_binary, to_json_string, Addr, Api, BalanceResponse, BankMsg,
+    BankQuery, Binary, BlockInfo, Coin, CosmosMsg, CustomMsg, CustomQuery, Empty, Event, Order,
+    Querier, QuerierResult, StdError, StdResult, Storage, Uint128,
 };
 use cw_multi_test::{
-    App, AppResponse, BankKeeper, BankSudo, BasicAppBuilder, CosmosRouter, Module, WasmKeeper,
+    App, AppResponse, BankSudo, CosmosRouter, DistributionKeeper, Executor, GovFailingModule,
+    IbcFailingModule, Module, StakeKeeper, WasmKeeper,
 };
-use cw_storage_plus::Map;
+use cw_storage_plus::{Bound, Item, Map};
 
 use token_bindings::{
-    AdminResponse, CreateDenomResponse, DenomsByCreatorResponse, FullDenomResponse, Metadata,
-    MetadataResponse, Params, ParamsResponse, TokenFactoryMsg, TokenFactoryMsgOptions,
-    TokenFactoryQuery, TokenFactoryQueryEnum,
+    AdminResponse, AllDenomsResponse, BeforeSendHookAddressResponse, CreateDenomResponse,
+    DenomsByCreatorResponse, FactoryDenom, FullDenomResponse, Metadata, MetadataResponse, Params,
+    ParamsResponse, TokenFactoryMsg, TokenFactoryMsgOptions, TokenFactoryQuery,
+    TokenFactoryQueryEnum, TotalSupplyResponse,
 };
 
+use crate::bank::TokenFactoryBank;
+use crate::builder::TokenFactoryAppBuilder;
 use crate::error::ContractError;
+use crate::events::*;
+use crate::genesis::{GenesisDenom, TokenFactoryGenesis};
+use crate::stargate::{StargateDenomAuthorityMetadata, TokenFactoryStargate};
 
-#[derive(Default)]
+#[derive(Clone)]
 pub struct TokenFactoryModule {
-    denom_creation_fee: Vec<Coin>,
+    params: Params,
+    fee_collector: Addr,
 }
 
+/// Receives the denom creation fees unless configured otherwise.
+/// Stands in for the community pool the chain module funds.
+pub const DEFAULT_FEE_COLLECTOR: &str = "fee_collector";
+
 /// How many seconds per block
 /// (when we increment block.height, use this multiplier for block.time)
 pub const BLOCK_TIME: u64 = 5;
 
-// map denom to metadata
-const METADATA: Map<&str, Metadata> = Map::new("metadata");
+// map denom to metadata, which is mirrored into the bank module with cosmwasm_1_3.
+// Not "metadata", as the bank module stores its denom metadata there.
+const METADATA: Map<&str, Metadata> = Map::new("factory_metadata");
 
-// map denom to admin
+// map denom to admin, which is empty (like on chain) once it was renounced
 const ADMIN: Map<&str, Addr> = Map::new("admin");
 
-// map creator to denoms
-const DENOMS_BY_CREATOR: Map<&Addr, Vec<String>> = Map::new("denom");
+// index of denoms by creator, iterated in denom order like on chain
+const DENOMS_BY_CREATOR: Map<(&Addr, &str), Empty> = Map::new("denom");
+
+// map denom to the contract called before every transfer of it
+pub(crate) const BEFORE_SEND_HOOK: Map<&str, Addr> = Map::new("before_send_hook");
+
+// map denom to total supply, updated by the bank on every mint and burn
+const SUPPLY: Map<&str, Uint128> = Map::new("supply");
+
+// params set by governance, overriding the ones the module was configured with
+const PARAMS: Item<Params> = Item::new("params");
+
+// total gas consumed for denom creation, as multi-test has no gas meter
+const CREATION_GAS_CONSUMED: Item<u64> = Item::new("creation_gas_consumed");
+
+// whether governance disabled the creation of new denoms
+const CREATION_DISABLED: Item<bool> = Item::new("creation_disabled");
+
+/// Privileged actions of the token factory module, like the ones
+/// governance proposals can execute on chain.
+/// Run them with `TokenFactoryApp::sudo_token_factory`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenFactorySudo {
+    /// Replaces the module params.
+    /// Emits no event, like a param change proposal, which the chain module doesn't see.
+    UpdateParams { params: Params },
+    /// Makes `new_admin` the admin of an existing denom,
+    /// or leaves it without admin if `new_admin` is empty
+    ForceSetAdmin { denom: String, new_admin: String },
+    /// Sets the metadata of an existing denom
+    ForceSetMetadata { denom: String, metadata: Metadata },
+    /// Rejects (or accepts again) all `CreateDenom` messages.
+    /// Emits no event, as the chain module has no such switch to emit one for.
+    DisableCreation { disabled: bool },
+}
+
+/// Page size for DenomsByCreator and AllDenoms if no limit is given
+pub const DEFAULT_DENOMS_LIMIT: u32 = 10;
+/// Largest page DenomsByCreator and AllDenoms return, bigger limits are capped to this
+pub const MAX_DENOMS_LIMIT: u32 = 100;
+
+impl Default for TokenFactoryModule {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
 
 impl TokenFactoryModule {
     pub fn new(denom_creation_fee: Vec<Coin>) -> Self {
-        Self { denom_creation_fee }
+        Self::with_params(Params {
+            denom_creation_fee,
+            denom_creation_gas_consume: 0,
+        })
     }
 
-    fn build_denom(&self, creator: &Addr, subdenom: &str) -> Result<String, ContractError> {
-        // Minimum validation checks on the full denom.
-        // https://github.com/cosmos/cosmos-sdk/blob/2646b474c7beb0c93d4fafd395ef345f41afc251/types/coin.go#L706-L711
-        // https://github.com/cosmos/cosmos-sdk/blob/2646b474c7beb0c93d4fafd395ef345f41afc251/types/coin.go#L677
-        let full_denom = format!("factory/{}/{}", creator, subdenom);
-        if full_denom.len() < 3
-            || full_denom.len() > 128
-            || creator.as_str().contains('/')
-            || subdenom.len() > 44
-            || creator.as_str().len() > 75
+    pub fn with_params(params: Params) -> Self {
+        Self {
+            params,
+            fee_collector: Addr::unchecked(DEFAULT_FEE_COLLECTOR),
+        }
+    }
+
+    /// Sends the denom creation fees to this address instead of `DEFAULT_FEE_COLLECTOR`
+    pub fn with_fee_collector(mut self, fee_collector: Addr) -> Self {
+        self.fee_collector = fee_collector;
+        self
+    }
+
+    pub fn fee_collector(&self) -> &Addr {
+        &self.fee_collector
+    }
+
+    /// The params set by governance, or the ones the module was configured with
+    pub fn params(&self, storage: &dyn Storage) -> StdResult<Params> {
+        Ok(PARAMS
+            .may_load(storage)?
+            .unwrap_or_else(|| self.params.clone()))
+    }
+
+    /// Administration function for creating denoms in genesis.
+    /// Like `CreateDenom`, but with any admin and without charging the fee.
+    pub fn init_denom(
+        &self,
+        storage: &mut dyn Storage,
+        creator: &Addr,
+        subdenom: &str,
+        admin: &Addr,
+        metadata: Option<Metadata>,
+    ) -> AnyResult<String> {
+        let denom = self.build_denom(creator, subdenom)?;
+        if ADMIN.may_load(storage, &denom)?.is_some() {
+            return Err(ContractError::TokenExists.into());
+        }
+        if let Some(md) = &metadata {
+            validate_metadata(&denom, md)?;
+        }
+        ADMIN.save(storage, &denom, admin)?;
+        DENOMS_BY_CREATOR.save(storage, (creator, &denom), &Empty {})?;
+        if let Some(md) = metadata {
+            save_metadata(storage, &denom, &md)?;
+        }
+        Ok(denom)
+    }
+
+    /// Administration function for adding genesis balances of factory denoms
+    /// to their total supply. Other denoms are ignored.
+    pub fn init_supply(&self, storage: &mut dyn Storage, amount: &[Coin]) -> AnyResult<()> {
+        increase_supply(storage, amount)
+    }
+
+    /// Loads the params, denoms, admins and metadata of a genesis on top of the current state.
+    /// Fails without changing anything if a denom is invalid, listed twice or exists already.
+    pub fn import_genesis(
+        &self,
+        storage: &mut dyn Storage,
+        genesis: TokenFactoryGenesis,
+    ) -> AnyResult<()> {
+        let mut denoms = BTreeMap::new();
+        for GenesisDenom {
+            denom,
+            authority_metadata,
+        } in genesis.factory_denoms
         {
-            return Err(ContractError::InvalidFullDenom { full_denom });
+            let factory_denom: FactoryDenom = denom.parse().map_err(ContractError::from)?;
+            if denoms.contains_key(&factory_denom) || ADMIN.has(storage, &denom) {
+                return Err(ContractError::TokenExists.into());
+            }
+            let metadata = genesis
+                .denom_metadata
+                .iter()
+                .find(|md| md.base.as_deref() == Some(denom.as_str()))
+                .cloned();
+            if let Some(md) = &metadata {
+                validate_metadata(&denom, md)?;
+            }
+            let admin = Addr::unchecked(authority_metadata.admin);
+            denoms.insert(factory_denom, (admin, metadata));
+        }
+
+        // the whole genesis is valid, so nothing below fails half way
+        if let Some(params) = genesis.params {
+            PARAMS.save(storage, &params.into())?;
+        }
+        for (factory_denom, (admin, metadata)) in denoms {
+            self.init_denom(
+                storage,
+                factory_denom.creator(),
+                factory_denom.subdenom(),
+                &admin,
+                metadata,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Dumps the params, denoms, admins and metadata, e.g. to compare them with
+    /// a chain's or to load them into another app with `import_genesis`
+    pub fn export_genesis(&self, storage: &dyn Storage) -> AnyResult<TokenFactoryGenesis> {
+        let factory_denoms = ADMIN
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (denom, admin) = item?;
+                Ok(GenesisDenom {
+                    denom,
+                    authority_metadata: StargateDenomAuthorityMetadata {
+                        admin: admin.into_string(),
+                    },
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        let denom_metadata = METADATA
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, metadata)| metadata))
+            .collect::<StdResult<_>>()?;
+        Ok(TokenFactoryGenesis {
+            params: Some(self.params(storage)?.into()),
+            factory_denoms,
+            denom_metadata,
+        })
+    }
+
+    /// Describes every inconsistency of the module's state, given the sum of all balances
+    /// of every denom in the bank. See `TokenFactoryApp::assert_token_factory_invariants`.
+    pub fn invariant_violations(
+        &self,
+        storage: &dyn Storage,
+        balances: &BTreeMap<String, Uint128>,
+    ) -> StdResult<Vec<String>> {
+        let mut violations = vec![];
+        let admins: BTreeMap<String, Addr> = ADMIN
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+
+        for denom in admins.keys() {
+            match denom.parse::<FactoryDenom>() {
+                Ok(factory_denom) => {
+                    if !DENOMS_BY_CREATOR.has(storage, (factory_denom.creator(), denom)) {
+                        violations.push(format!(
+                            "{} is not listed under its creator {}",
+                            denom,
+                            factory_denom.creator()
+                        ));
+                    }
+                }
+                Err(err) => violations.push(format!("{} is invalid: {}", denom, err)),
+            }
+            let supply = SUPPLY.may_load(storage, denom)?.unwrap_or_default();
+            let balance = balances.get(denom).copied().unwrap_or_default();
+            if supply != balance {
+                violations.push(format!(
+                    "total supply of {} is {}, but its balances add up to {}",
+                    denom, supply, balance
+                ));
+            }
+        }
+        for denom in balances.keys() {
+            if denom.parse::<FactoryDenom>().is_ok() && !admins.contains_key(denom) {
+                violations.push(format!("{} has balances, but was never created", denom));
+            }
+        }
+
+        for item in DENOMS_BY_CREATOR.keys(storage, None, None, Order::Ascending) {
+            let (creator, denom) = item?;
+            let created_by = denom.parse::<FactoryDenom>().ok();
+            if !admins.contains_key(&denom) {
+                violations.push(format!(
+                    "{} is listed under {}, but does not exist",
+                    denom, creator
+                ));
+            } else if created_by.is_some_and(|created_by| created_by.creator() != creator) {
+                violations.push(format!(
+                    "{} is listed under {}, who did not create it",
+                    denom, creator
+                ));
+            }
+        }
+
+        let denoms_with = [
+            (
+                "metadata",
+                METADATA.keys(storage, None, None, Order::Ascending),
+            ),
+            (
+                "total supply",
+                SUPPLY.keys(storage, None, None, Order::Ascending),
+            ),
+            (
+                "before send hook",
+                BEFORE_SEND_HOOK.keys(storage, None, None, Order::Ascending),
+            ),
+        ];
+        for (what, denoms) in denoms_with {
+            for denom in denoms {
+                let denom = denom?;
+                if !admins.contains_key(&denom) {
+                    violations.push(format!("{} has a {}, but does not exist", denom, what));
+                }
+            }
+        }
+        Ok(violations)
+    }
+
+    /// Gas consumed by all denom creations so far, according to
+    /// `denom_creation_gas_consume` at the time
+    pub fn denom_creation_gas_consumed(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(CREATION_GAS_CONSUMED.may_load(storage)?.unwrap_or_default())
+    }
+
+    fn consume_creation_gas(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        let gas = self.params(storage)?.denom_creation_gas_consume;
+        let consumed = self.denom_creation_gas_consumed(storage)?;
+        CREATION_GAS_CONSUMED.save(storage, &consumed.saturating_add(gas))
+    }
+
+    /// Moves the denom creation fee from the creator to the fee collector
+    fn charge_creation_fee<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        creator: &Addr,
+    ) -> AnyResult<()>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        let denom_creation_fee = self.params(storage)?.denom_creation_fee;
+        if denom_creation_fee.is_empty() {
+            return Ok(());
         }
-        Ok(full_denom)
+        for required in &denom_creation_fee {
+            let query = BankQuery::Balance {
+                address: creator.to_string(),
+                denom: required.denom.clone(),
+            };
+            let BalanceResponse { amount: available } =
+                from_json(router.query(api, storage, block, query.into())?)?;
+            if available.amount < required.amount {
+                return Err(ContractError::InsufficientCreationFee {
+                    required: required.clone(),
+                    available,
+                }
+                .into());
+            }
+        }
+        let fee = BankMsg::Send {
+            to_address: self.fee_collector.to_string(),
+            amount: denom_creation_fee,
+        };
+        router.execute(api, storage, block, creator.clone(), fee.into())?;
+        Ok(())
+    }
+
+    /// Builds factory/{creator}/{subdenom} under the rules of the chain, see `FactoryDenom`
+    fn build_denom(&self, creator: &Addr, subdenom: &str) -> Result<String, ContractError> {
+        Ok(FactoryDenom::new(creator.as_str(), subdenom)?.to_string())
     }
 }
 
 impl Module for TokenFactoryModule {
     type ExecT = TokenFactoryMsg;
     type QueryT = TokenFactoryQuery;
-    type SudoT = Empty;
+    type SudoT = TokenFactorySudo;
 
     // Builds a mock rust implementation of the expected Token Factory functionality for testing
     fn execute<ExecC, QueryC>(
@@ -85,29 +397,37 @@ impl Module for TokenFactoryModule {
     {
         match msg {
             TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom { subdenom, metadata }) => {
+                if CREATION_DISABLED.may_load(storage)?.unwrap_or_default() {
+                    return Err(ContractError::CreationDisabled.into());
+                }
                 let new_token_denom = self.build_denom(&sender, &subdenom)?;
 
                 // errors if the denom was already created
                 if ADMIN.may_load(storage, &new_token_denom)?.is_some() {
                     return Err(ContractError::TokenExists.into());
                 }
+                if let Some(md) = &metadata {
+                    validate_metadata(&new_token_denom, md)?;
+                }
+                self.charge_creation_fee(api, storage, router, block, &sender)?;
+                self.consume_creation_gas(storage)?;
+
                 ADMIN.save(storage, &new_token_denom, &sender)?;
 
-                let mut denoms = DENOMS_BY_CREATOR
-                    .may_load(storage, &sender)?
-                    .unwrap_or_default();
-                denoms.push(new_token_denom.clone());
-                DENOMS_BY_CREATOR.save(storage, &sender, &denoms)?;
+                DENOMS_BY_CREATOR.save(storage, (&sender, &new_token_denom), &Empty {})?;
 
                 // set metadata if provided
                 if let Some(md) = metadata {
-                    METADATA.save(storage, &new_token_denom, &md)?;
+                    save_metadata(storage, &new_token_denom, &md)?;
                 }
 
+                let event = Event::new(EVENT_CREATE_DENOM)
+                    .add_attribute(ATTR_CREATOR, &sender)
+                    .add_attribute(ATTR_NEW_TOKEN_DENOM, &new_token_denom);
                 let data = Some(CreateDenomResponse { new_token_denom }.encode()?);
                 Ok(AppResponse {
                     data,
-                    events: vec![],
+                    events: vec![event],
                 })
             }
             TokenFactoryMsg::Token(TokenFactoryMsgOptions::MintTokens {
@@ -115,18 +435,18 @@ impl Module for TokenFactoryModule {
                 amount,
                 mint_to_address,
             }) => {
-                // ensure we are admin of this denom (and it exists)
-                let admin = ADMIN
-                    .may_load(storage, &denom)?
-                    .ok_or(ContractError::TokenDoesntExist)?;
-                if admin != sender {
-                    return Err(ContractError::NotTokenAdmin.into());
-                }
+                ensure_admin(storage, &denom, &sender)?;
+                // like the chain, an empty address mints to the sender
+                let mint_to_address = or_sender(mint_to_address, &sender);
+                let event = Event::new(EVENT_MINT)
+                    .add_attribute(ATTR_MINT_TO_ADDRESS, &mint_to_address)
+                    .add_attribute(ATTR_AMOUNT, coin(amount.u128(), &denom).to_string());
                 let mint = BankSudo::Mint {
                     to_address: mint_to_address,
                     amount: coins(amount.u128(), &denom),
                 };
-                let res = router.sudo(api, storage, block, mint.into())?;
+                let mut res = router.sudo(api, storage, block, mint.into())?;
+                res.events.push(event);
                 Ok(res)
             }
             TokenFactoryMsg::Token(TokenFactoryMsgOptions::BurnTokens {
@@ -134,16 +454,23 @@ impl Module for TokenFactoryModule {
                 amount,
                 burn_from_address,
             }) => {
+                ensure_admin(storage, &denom, &sender)?;
+                // like the chain, an empty address burns from the sender
+                let burn_from_address = or_sender(burn_from_address, &sender);
+                let event = Event::new(EVENT_BURN)
+                    .add_attribute(ATTR_BURN_FROM_ADDRESS, &burn_from_address)
+                    .add_attribute(ATTR_AMOUNT, coin(amount.u128(), &denom).to_string());
                 let msg = BankMsg::Burn {
                     amount: coins(amount.u128(), &denom),
                 };
-                let res = router.execute(
+                let mut res = router.execute(
                     api,
                     storage,
                     block,
                     Addr::unchecked(burn_from_address),
                     msg.into(),
                 )?;
+                res.events.push(event);
                 Ok(res)
             }
             TokenFactoryMsg::Token(TokenFactoryMsgOptions::ForceTransfer {
@@ -152,63 +479,127 @@ impl Module for TokenFactoryModule {
                 from_address,
                 to_address,
             }) => {
+                // this also ensures only factory denoms can be moved
+                ensure_admin(storage, &denom, &sender)?;
+                let event = Event::new(EVENT_FORCE_TRANSFER)
+                    .add_attribute(ATTR_TRANSFER_FROM_ADDRESS, &from_address)
+                    .add_attribute(ATTR_TRANSFER_TO_ADDRESS, &to_address)
+                    .add_attribute(ATTR_AMOUNT, coin(amount.u128(), &denom).to_string());
                 let msg = BankMsg::Send {
                     to_address,
                     amount: coins(amount.u128(), &denom),
                 };
-                let res = router.execute(
+                let mut res = router.execute(
                     api,
                     storage,
                     block,
                     Addr::unchecked(from_address),
                     msg.into(),
                 )?;
+                res.events.push(event);
                 Ok(res)
             }
             TokenFactoryMsg::Token(TokenFactoryMsgOptions::ChangeAdmin {
                 denom,
                 new_admin_address,
             }) => {
-                // ensure we are admin of this denom (and it exists)
-                let admin = ADMIN
-                    .may_load(storage, &denom)?
-                    .ok_or(ContractError::TokenDoesntExist)?;
-                if admin != sender {
-                    return Err(ContractError::NotTokenAdmin.into());
-                }
-                // and new admin is valid
-                let new_admin = api.addr_validate(&new_admin_address)?;
+                ensure_admin(storage, &denom, &sender)?;
+                // and new admin is valid, unless the admin is renounced
+                let new_admin = validate_admin(api, &new_admin_address)?;
                 ADMIN.save(storage, &denom, &new_admin)?;
-                Ok(AppResponse::default())
+                let event = Event::new(EVENT_CHANGE_ADMIN)
+                    .add_attribute(ATTR_DENOM, denom)
+                    .add_attribute(ATTR_NEW_ADMIN, new_admin);
+                Ok(AppResponse {
+                    events: vec![event],
+                    data: None,
+                })
             }
             TokenFactoryMsg::Token(TokenFactoryMsgOptions::SetMetadata { denom, metadata }) => {
-                // ensure we are admin of this denom (and it exists)
-                let admin = ADMIN
-                    .may_load(storage, &denom)?
-                    .ok_or(ContractError::TokenDoesntExist)?;
-                if admin != sender {
-                    return Err(ContractError::NotTokenAdmin.into());
+                ensure_admin(storage, &denom, &sender)?;
+                validate_metadata(&denom, &metadata)?;
+                save_metadata(storage, &denom, &metadata)?;
+                let event = Event::new(EVENT_SET_DENOM_METADATA)
+                    .add_attribute(ATTR_DENOM, denom)
+                    .add_attribute(ATTR_DENOM_METADATA, to_json_string(&metadata)?);
+                Ok(AppResponse {
+                    events: vec![event],
+                    data: None,
+                })
+            }
+            TokenFactoryMsg::Token(TokenFactoryMsgOptions::SetBeforeSendHook {
+                denom,
+                contract_addr,
+            }) => {
+                ensure_admin(storage, &denom, &sender)?;
+                // an empty address removes the hook
+                if contract_addr.is_empty() {
+                    BEFORE_SEND_HOOK.remove(storage, &denom);
+                } else {
+                    let contract = api.addr_validate(&contract_addr)?;
+                    BEFORE_SEND_HOOK.save(storage, &denom, &contract)?;
                 }
-                // FIXME: add validation of metadata
-                METADATA.save(storage, &denom, &metadata)?;
-                Ok(AppResponse::default())
+                let event = Event::new(EVENT_SET_BEFORE_SEND_HOOK)
+                    .add_attribute(ATTR_DENOM, denom)
+                    .add_attribute(ATTR_BEFORE_SEND_HOOK_ADDRESS, contract_addr);
+                Ok(AppResponse {
+                    events: vec![event],
+                    data: None,
+                })
             }
         }
     }
 
     fn sudo<ExecC, QueryC>(
         &self,
-        _api: &dyn Api,
-        _storage: &mut dyn Storage,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
         _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
         _block: &BlockInfo,
-        _msg: Self::SudoT,
+        msg: Self::SudoT,
     ) -> AnyResult<AppResponse>
     where
         ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
         QueryC: CustomQuery + DeserializeOwned + 'static,
     {
-        bail!("sudo not implemented for TokenFactoryModule")
+        match msg {
+            TokenFactorySudo::UpdateParams { params } => {
+                PARAMS.save(storage, &params)?;
+                Ok(AppResponse::default())
+            }
+            TokenFactorySudo::ForceSetAdmin { denom, new_admin } => {
+                if ADMIN.may_load(storage, &denom)?.is_none() {
+                    return Err(ContractError::TokenDoesntExist.into());
+                }
+                let new_admin = validate_admin(api, &new_admin)?;
+                ADMIN.save(storage, &denom, &new_admin)?;
+                let event = Event::new(EVENT_CHANGE_ADMIN)
+                    .add_attribute(ATTR_DENOM, denom)
+                    .add_attribute(ATTR_NEW_ADMIN, new_admin);
+                Ok(AppResponse {
+                    events: vec![event],
+                    data: None,
+                })
+            }
+            TokenFactorySudo::ForceSetMetadata { denom, metadata } => {
+                if ADMIN.may_load(storage, &denom)?.is_none() {
+                    return Err(ContractError::TokenDoesntExist.into());
+                }
+                validate_metadata(&denom, &metadata)?;
+                save_metadata(storage, &denom, &metadata)?;
+                let event = Event::new(EVENT_SET_DENOM_METADATA)
+                    .add_attribute(ATTR_DENOM, denom)
+                    .add_attribute(ATTR_DENOM_METADATA, to_json_string(&metadata)?);
+                Ok(AppResponse {
+                    events: vec![event],
+                    data: None,
+                })
+            }
+            TokenFactorySudo::DisableCreation { disabled } => {
+                CREATION_DISABLED.save(storage, &disabled)?;
+                Ok(AppResponse::default())
+            }
+        }
     }
 
     fn query(
@@ -234,24 +625,158 @@ impl Module for TokenFactoryModule {
                 Ok(to_json_binary(&MetadataResponse { metadata })?)
             }
             TokenFactoryQuery::Token(TokenFactoryQueryEnum::Admin { denom }) => {
-                let admin = ADMIN.load(storage, &denom)?.to_string();
+                // like the chain, denoms without admin return an empty one
+                let admin = ADMIN
+                    .may_load(storage, &denom)?
+                    .map(String::from)
+                    .unwrap_or_default();
                 Ok(to_json_binary(&AdminResponse { admin })?)
             }
-            TokenFactoryQuery::Token(TokenFactoryQueryEnum::DenomsByCreator { creator }) => {
+            TokenFactoryQuery::Token(TokenFactoryQueryEnum::DenomsByCreator {
+                creator,
+                start_after,
+                limit,
+            }) => {
                 let creator = api.addr_validate(&creator)?;
-                let denoms = DENOMS_BY_CREATOR
-                    .may_load(storage, &creator)?
-                    .unwrap_or_default();
-                Ok(to_json_binary(&DenomsByCreatorResponse { denoms })?)
+                let (denoms, next_start_after) = paginate(
+                    DENOMS_BY_CREATOR.prefix(&creator).keys(
+                        storage,
+                        bound(&start_after),
+                        None,
+                        Order::Ascending,
+                    ),
+                    limit,
+                )?;
+                Ok(to_json_binary(&DenomsByCreatorResponse {
+                    denoms,
+                    next_start_after,
+                })?)
             }
             TokenFactoryQuery::Token(TokenFactoryQueryEnum::Params {}) => {
-                let params = Params {
-                    denom_creation_fee: self.denom_creation_fee.clone(),
-                };
+                let params = self.params(storage)?;
                 Ok(to_json_binary(&ParamsResponse { params })?)
             }
+            TokenFactoryQuery::Token(TokenFactoryQueryEnum::AllDenoms { start_after, limit }) => {
+                let (denoms, next_start_after) = paginate(
+                    ADMIN.keys(storage, bound(&start_after), None, Order::Ascending),
+                    limit,
+                )?;
+                Ok(to_json_binary(&AllDenomsResponse {
+                    denoms,
+                    next_start_after,
+                })?)
+            }
+            TokenFactoryQuery::Token(TokenFactoryQueryEnum::BeforeSendHookAddress { denom }) => {
+                let contract_addr = BEFORE_SEND_HOOK
+                    .may_load(storage, &denom)?
+                    .map(String::from);
+                Ok(to_json_binary(&BeforeSendHookAddressResponse {
+                    contract_addr,
+                })?)
+            }
+            TokenFactoryQuery::Token(TokenFactoryQueryEnum::TotalSupply { denom }) => {
+                let supply = SUPPLY.may_load(storage, &denom)?.unwrap_or_default();
+                let amount = coin(supply.u128(), denom);
+                Ok(to_json_binary(&TotalSupplyResponse { amount })?)
+            }
+        }
+    }
+}
+
+/// Adds minted amounts of factory denoms to their total supply. Other denoms are ignored.
+pub(crate) fn increase_supply(storage: &mut dyn Storage, amount: &[Coin]) -> AnyResult<()> {
+    for coin in amount {
+        if ADMIN.may_load(storage, &coin.denom)?.is_none() {
+            continue;
+        }
+        let supply = SUPPLY.may_load(storage, &coin.denom)?.unwrap_or_default();
+        SUPPLY.save(storage, &coin.denom, &supply.checked_add(coin.amount)?)?;
+    }
+    Ok(())
+}
+
+/// Subtracts burned amounts of factory denoms from their total supply.
+/// Other denoms are ignored.
+pub(crate) fn decrease_supply(storage: &mut dyn Storage, amount: &[Coin]) -> AnyResult<()> {
+    for coin in amount {
+        if ADMIN.may_load(storage, &coin.denom)?.is_none() {
+            continue;
+        }
+        let supply = SUPPLY.may_load(storage, &coin.denom)?.unwrap_or_default();
+        SUPPLY.save(storage, &coin.denom, &supply.checked_sub(coin.amount)?)?;
+    }
+    Ok(())
+}
+
+/// The new admin of a denom, which is empty if the admin is renounced
+fn validate_admin(api: &dyn Api, new_admin: &str) -> StdResult<Addr> {
+    match new_admin.is_empty() {
+        true => Ok(Addr::unchecked("")),
+        false => api.addr_validate(new_admin),
+    }
+}
+
+/// The address, or the sender if it is empty
+fn or_sender(address: String, sender: &Addr) -> String {
+    match address.is_empty() {
+        true => sender.to_string(),
+        false => address,
+    }
+}
+
+/// Ensures the denom exists and sender is its admin
+fn ensure_admin(storage: &dyn Storage, denom: &str, sender: &Addr) -> Result<(), ContractError> {
+    let admin = ADMIN
+        .may_load(storage, denom)?
+        .ok_or(ContractError::TokenDoesntExist)?;
+    if admin != sender {
+        return Err(ContractError::NotTokenAdmin);
+    }
+    Ok(())
+}
+
+/// Stores the metadata of a factory denom, where the bank module sees it as well
+fn save_metadata(storage: &mut dyn Storage, denom: &str, metadata: &Metadata) -> AnyResult<()> {
+    METADATA.save(storage, denom, metadata)?;
+    #[cfg(feature = "cosmwasm_1_3")]
+    crate::bank::set_denom_metadata(storage, denom, metadata)?;
+    Ok(())
+}
+
+/// Applies the bank module's rules, and checks uri_hash is a hex encoded SHA-256 hash
+fn validate_metadata(denom: &str, metadata: &Metadata) -> Result<(), ContractError> {
+    metadata.validate(denom)?;
+    if let Some(uri_hash) = &metadata.uri_hash {
+        let is_sha256 = uri_hash.len() == 64 && uri_hash.chars().all(|c| c.is_ascii_hexdigit());
+        if !uri_hash.is_empty() && !is_sha256 {
+            return Err(ContractError::InvalidUriHash {
+                uri_hash: uri_hash.clone(),
+            });
         }
     }
+    Ok(())
+}
+
+fn bound(start_after: &Option<String>) -> Option<Bound<'_, &str>> {
+    start_after.as_deref().map(Bound::exclusive)
+}
+
+/// Takes one page of denoms from the iterator,
+/// along with the start of the next page if there is one
+fn paginate(
+    denoms: impl Iterator<Item = StdResult<String>>,
+    limit: Option<u32>,
+) -> StdResult<(Vec<String>, Option<String>)> {
+    let limit = limit.unwrap_or(DEFAULT_DENOMS_LIMIT).min(MAX_DENOMS_LIMIT) as usize;
+    // load one extra denom to know if there is a next page
+    let mut denoms = denoms.take(limit + 1).collect::<StdResult<Vec<_>>>()?;
+    let next_start_after = if denoms.len() > limit {
+        denoms.truncate(limit);
+        denoms.last().cloned()
+    } else {
+        None
+    };
+    Ok((denoms, next_start_after))
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -265,32 +790,51 @@ pub enum TokenFactoryError {
 }
 
 pub type TokenFactoryAppWrapped = App<
-    BankKeeper,
+    TokenFactoryBank,
     MockApi,
     MockStorage,
     TokenFactoryModule,
     WasmKeeper<TokenFactoryMsg, TokenFactoryQuery>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovFailingModule,
+    TokenFactoryStargate,
 >;
 
-pub struct TokenFactoryApp(TokenFactoryAppWrapped);
+pub struct TokenFactoryApp {
+    pub(crate) app: TokenFactoryAppWrapped,
+    /// Whether to assert the invariants after every execution,
+    /// see `TokenFactoryAppBuilder::with_invariant_checks`
+    pub(crate) check_invariants: bool,
+}
 
 impl Deref for TokenFactoryApp {
     type Target = TokenFactoryAppWrapped;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.app
     }
 }
 
 impl DerefMut for TokenFactoryApp {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.app
     }
 }
 
 impl Querier for TokenFactoryApp {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
-        self.0.raw_query(bin_request)
+        self.app.raw_query(bin_request)
+    }
+}
+
+/// Shadows `App::execute`, and so all `Executor` helpers, to check the invariants if enabled
+impl Executor<TokenFactoryMsg> for TokenFactoryApp {
+    fn execute(&mut self, sender: Addr, msg: CosmosMsg<TokenFactoryMsg>) -> AnyResult<AppResponse> {
+        let res = self.app.execute(sender, msg);
+        self.check_invariants();
+        res
     }
 }
 
@@ -301,20 +845,130 @@ impl Default for TokenFactoryApp {
 }
 
 impl TokenFactoryApp {
+    /// An app with default params and no genesis state,
+    /// use `TokenFactoryAppBuilder` to configure them
     pub fn new() -> Self {
-        Self(
-            BasicAppBuilder::<TokenFactoryMsg, TokenFactoryQuery>::new_custom()
-                .with_custom(TokenFactoryModule {
-                    denom_creation_fee: vec![],
-                })
-                .build(|_router, _, _storage| {
-                    // router.custom.set_owner(storage, &owner).unwrap();
-                }),
-        )
+        TokenFactoryAppBuilder::new().build()
+    }
+
+    /// The address receiving denom creation fees
+    pub fn fee_collector(&self) -> Addr {
+        self.read_module(|router, _, _| router.custom.fee_collector().clone())
+    }
+
+    /// Gas consumed by all denom creations so far. Multi-test does not meter gas,
+    /// so this is the only place `denom_creation_gas_consume` shows up.
+    pub fn denom_creation_gas_consumed(&self) -> u64 {
+        self.read_module(|router, _, storage| {
+            router.custom.denom_creation_gas_consumed(storage).unwrap()
+        })
+    }
+
+    /// Loads a genesis on top of the current state, see `TokenFactoryModule::import_genesis`
+    pub fn import_genesis(&mut self, genesis: TokenFactoryGenesis) -> AnyResult<()> {
+        self.init_modules(|router, _, storage| router.custom.import_genesis(storage, genesis))
+    }
+
+    /// The current state of the token factory in the layout of the chain's genesis
+    pub fn export_genesis(&self) -> TokenFactoryGenesis {
+        self.read_module(|router, _, storage| router.custom.export_genesis(storage).unwrap())
+    }
+
+    /// Runs a privileged action on the token factory, like a governance proposal would
+    pub fn sudo_token_factory(&mut self, msg: TokenFactorySudo) -> AnyResult<AppResponse> {
+        let block = self.block_info();
+        let res = self.init_modules(|router, api, storage| {
+            router.custom.sudo(api, storage, router, &block, msg)
+        });
+        self.check_invariants();
+        res
+    }
+
+    /// Like `App::execute_multi`, checking the invariants if enabled
+    pub fn execute_multi(
+        &mut self,
+        sender: Addr,
+        msgs: Vec<CosmosMsg<TokenFactoryMsg>>,
+    ) -> AnyResult<Vec<AppResponse>> {
+        let res = self.app.execute_multi(sender, msgs);
+        self.check_invariants();
+        res
+    }
+
+    /// Panics unless the state of the token factory is consistent with itself and the bank:
+    /// - the total supply of every factory denom is the sum of its balances
+    /// - there are no balances of factory denoms that were never created
+    /// - every denom is a valid factory denom, listed under its creator
+    /// - the creator index, metadata, supply and hooks only refer to existing denoms
+    pub fn assert_token_factory_invariants(&self) {
+        let balances = self.balances_by_denom().unwrap();
+        let violations = self
+            .read_module(|router, _, storage| {
+                router.custom.invariant_violations(storage, &balances)
+            })
+            .unwrap();
+        assert!(
+            violations.is_empty(),
+            "token factory invariants violated:\n{}",
+            violations.join("\n")
+        );
+    }
+
+    /// The sum of all balances of every denom, queried from the bank
+    fn balances_by_denom(&self) -> StdResult<BTreeMap<String, Uint128>> {
+        let accounts = self.read_module(|router, _, storage| router.bank.accounts(storage))?;
+        let mut balances: BTreeMap<String, Uint128> = BTreeMap::new();
+        for account in accounts {
+            for coin in self.wrap().query_all_balances(account)? {
+                let total = balances.entry(coin.denom).or_default();
+                *total = total.checked_add(coin.amount)?;
+            }
+        }
+        Ok(balances)
+    }
+
+    pub(crate) fn check_invariants(&self) {
+        if self.check_invariants {
+            self.assert_token_factory_invariants();
+        }
+    }
+
+    /// Returns the first event of the given type in the response, if any.
+    /// Use the `events` module for the types emitted by the token factory.
+    pub fn find_event<'a>(res: &'a AppResponse, ty: &str) -> Option<&'a Event> {
+        res.events.iter().find(|event| event.ty == ty)
+    }
+
+    /// Returns the value of the attribute with the given key, if any
+    pub fn event_attribute<'a>(event: &'a Event, key: &str) -> Option<&'a str> {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.as_str())
+    }
+
+    /// Panics unless the response has an event of the given type
+    /// with (at least) all the given attributes
+    pub fn assert_event(res: &AppResponse, ty: &str, attributes: &[(&str, &str)]) {
+        let found = res
+            .events
+            .iter()
+            .filter(|event| event.ty == ty)
+            .any(|event| {
+                attributes
+                    .iter()
+                    .all(|(key, value)| Self::event_attribute(event, key) == Some(*value))
+            });
+        assert!(
+            found,
+            "no {} event with attributes {:?} in {:?}",
+            ty, attributes, res.events
+        );
     }
 
     pub fn block_info(&self) -> BlockInfo {
-        self.0.block_info()
+        self.app.block_info()
     }
 
     /// This advances BlockInfo by given number of blocks.
@@ -345,8 +999,18 @@ impl TokenFactoryApp {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::{Coin, Uint128};
+    use cosmwasm_std::Coin;
     use cw_multi_test::Executor;
+    use token_bindings::{MetadataError, TokenQuerier};
+
+    /// Valid metadata for `denom`, displayed as FUNDZ with 6 decimals
+    fn fundz_metadata(denom: &str) -> Metadata {
+        Metadata::builder(denom)
+            .display("FUNDZ", 6)
+            .name("Fundz")
+            .build()
+            .unwrap()
+    }
 
     #[test]
     fn mint_token() {
@@ -394,14 +1058,15 @@ mod tests {
         // create the token now
         let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
             subdenom: subdenom.to_string(),
-            metadata: Some(Metadata {
-                description: Some("Awesome token, get it now!".to_string()),
-                denom_units: vec![],
-                base: None,
-                display: Some("FUNDZ".to_string()),
-                name: Some("Fundz pays".to_string()),
-                symbol: Some("FUNDZ".to_string()),
-            }),
+            metadata: Some(
+                Metadata::builder(&denom)
+                    .description("Awesome token, get it now!")
+                    .display("FUNDZ", 6)
+                    .name("Fundz pays")
+                    .symbol("FUNDZ")
+                    .build()
+                    .unwrap(),
+            ),
         });
         app.execute(contract.clone(), create.into()).unwrap();
 
@@ -440,4 +1105,1142 @@ mod tests {
         let bob_amount = app.wrap().query_balance(bob_addr.as_str(), &denom).unwrap();
         assert_eq!(bob_amount, expected);
     }
+
+    #[test]
+    fn create_denom_returns_reply_data() {
+        let contract = Addr::unchecked("govner");
+        let mut app = TokenFactoryApp::new();
+
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        let res = app.execute(contract, create.into()).unwrap();
+
+        let CreateDenomResponse { new_token_denom } =
+            CreateDenomResponse::from_reply_data(res.data.unwrap()).unwrap();
+        assert_eq!(new_token_denom, "factory/govner/fundz");
+    }
+
+    #[test]
+    fn query_admin() {
+        let contract = Addr::unchecked("govner");
+        let mut app = TokenFactoryApp::new();
+
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        app.execute(contract.clone(), create.into()).unwrap();
+
+        let wrapper = app.wrap();
+        let querier = TokenQuerier::new(&wrapper);
+        let AdminResponse { admin } = querier.admin("factory/govner/fundz").unwrap();
+        assert_eq!(admin, contract.to_string());
+        let admin = querier.try_admin("factory/govner/fundz").unwrap();
+        assert_eq!(admin, Some(contract));
+
+        // never created
+        let AdminResponse { admin } = querier.admin("factory/govner/other").unwrap();
+        assert_eq!(admin, "");
+        assert_eq!(querier.try_admin("factory/govner/other").unwrap(), None);
+        // not a factory denom
+        assert_eq!(querier.try_admin("uosmo").unwrap(), None);
+    }
+
+    #[test]
+    fn paginate_denoms_by_creator() {
+        let contract = Addr::unchecked("govner");
+        let mut app = TokenFactoryApp::new();
+
+        let mut expected = vec![];
+        for i in 0..25 {
+            let subdenom = format!("fundz{:02}", i);
+            let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+                subdenom: subdenom.clone(),
+                metadata: None,
+            });
+            app.execute(contract.clone(), create.into()).unwrap();
+            expected.push(format!("factory/govner/{}", subdenom));
+        }
+        // denoms of other creators are not listed
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        app.execute(Addr::unchecked("other"), create.into())
+            .unwrap();
+
+        let wrapper = app.wrap();
+        let querier = TokenQuerier::new(&wrapper);
+
+        // default page size
+        let page = querier
+            .denoms_by_creator_page(contract.to_string(), None, None)
+            .unwrap();
+        assert_eq!(page.denoms, expected[..10]);
+        assert_eq!(page.next_start_after, Some(expected[9].clone()));
+
+        // explicit pages
+        let page = querier
+            .denoms_by_creator_page(contract.to_string(), page.next_start_after, Some(12))
+            .unwrap();
+        assert_eq!(page.denoms, expected[10..22]);
+        let page = querier
+            .denoms_by_creator_page(contract.to_string(), page.next_start_after, Some(12))
+            .unwrap();
+        assert_eq!(page.denoms, expected[22..]);
+        assert_eq!(page.next_start_after, None);
+
+        // iterate over everything
+        let all = querier
+            .denoms_by_creator_iter(contract.to_string(), Some(4))
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(all, expected);
+        let all = querier.denom_by_creator(contract.to_string()).unwrap();
+        assert_eq!(all.denoms, expected);
+        assert_eq!(all.next_start_after, None);
+
+        // unknown creators have no denoms
+        let page = querier.denom_by_creator("nobody".to_string()).unwrap();
+        assert_eq!(page.denoms, Vec::<String>::new());
+        assert_eq!(page.next_start_after, None);
+    }
+
+    #[test]
+    fn all_denoms_and_total_supply() {
+        let mut app = TokenFactoryApp::new();
+        let rcpt = Addr::unchecked("townies");
+
+        let mut expected = vec![];
+        for creator in ["alice", "bob", "carl"] {
+            for subdenom in ["one", "two"] {
+                let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+                    subdenom: subdenom.to_string(),
+                    metadata: None,
+                });
+                app.execute(Addr::unchecked(creator), create.into())
+                    .unwrap();
+                expected.push(format!("factory/{}/{}", creator, subdenom));
+            }
+        }
+
+        let wrapper = app.wrap();
+        let querier = TokenQuerier::new(&wrapper);
+        let page = querier.all_denoms_page(None, Some(4)).unwrap();
+        assert_eq!(page.denoms, expected[..4]);
+        assert_eq!(page.next_start_after, Some(expected[3].clone()));
+        let all = querier
+            .all_denoms_iter(Some(4))
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(all, expected);
+
+        // nothing minted yet
+        let denom = "factory/alice/one";
+        let supply = querier.total_supply(denom).unwrap().amount;
+        assert_eq!(supply, coin(0, denom));
+
+        // supply follows mints and burns
+        let mint =
+            TokenFactoryMsg::mint_contract_tokens(denom, Uint128::new(500), rcpt.to_string());
+        app.execute(Addr::unchecked("alice"), mint.clone().into())
+            .unwrap();
+        app.execute(Addr::unchecked("alice"), mint.into()).unwrap();
+        let burn =
+            TokenFactoryMsg::burn_contract_tokens(denom, Uint128::new(300), rcpt.to_string());
+        app.execute(Addr::unchecked("alice"), burn.into()).unwrap();
+
+        let wrapper = app.wrap();
+        let querier = TokenQuerier::new(&wrapper);
+        let supply = querier.total_supply(denom).unwrap().amount;
+        assert_eq!(supply, coin(700, denom));
+        let balance = app.wrap().query_balance(rcpt.as_str(), denom).unwrap();
+        assert_eq!(balance, supply);
+        // other denoms are not affected
+        let supply = querier.total_supply("factory/alice/two").unwrap().amount;
+        assert_eq!(supply, coin(0, "factory/alice/two"));
+    }
+
+    #[test]
+    fn set_before_send_hook() {
+        let contract = Addr::unchecked("govner");
+        let denom = "factory/govner/fundz";
+        let mut app = TokenFactoryApp::new();
+
+        let set_hook = TokenFactoryMsg::set_before_send_hook(denom, "hooker".to_string());
+        let err = app
+            .execute(contract.clone(), set_hook.clone().into())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::TokenDoesntExist
+        );
+
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        app.execute(contract.clone(), create.into()).unwrap();
+
+        // only the admin can set a hook
+        let err = app
+            .execute(Addr::unchecked("intruder"), set_hook.clone().into())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotTokenAdmin
+        );
+
+        let query_hook = |app: &TokenFactoryApp| {
+            TokenQuerier::new(&app.wrap())
+                .before_send_hook_address(denom)
+                .unwrap()
+                .contract_addr
+        };
+        assert_eq!(query_hook(&app), None);
+        app.execute(contract.clone(), set_hook.into()).unwrap();
+        assert_eq!(query_hook(&app), Some("hooker".to_string()));
+
+        // empty address removes it
+        let remove_hook = TokenFactoryMsg::set_before_send_hook(denom, String::new());
+        app.execute(contract, remove_hook.into()).unwrap();
+        assert_eq!(query_hook(&app), None);
+    }
+
+    mod hook_contract {
+        use cosmwasm_std::{
+            to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+            StdResult,
+        };
+        use cw_storage_plus::Item;
+        use token_bindings::{BeforeSendHookSudoMsg, TokenFactoryMsg, TokenFactoryQuery};
+
+        /// Every transfer the hook saw, as (from, to, amount)
+        const TRANSFERS: Item<Vec<(String, String, u128)>> = Item::new("transfers");
+
+        pub fn instantiate(
+            _deps: DepsMut<TokenFactoryQuery>,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response<TokenFactoryMsg>> {
+            Ok(Response::new())
+        }
+
+        pub fn execute(
+            _deps: DepsMut<TokenFactoryQuery>,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response<TokenFactoryMsg>> {
+            Ok(Response::new())
+        }
+
+        pub fn query(deps: Deps<TokenFactoryQuery>, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_json_binary(&TRANSFERS.may_load(deps.storage)?.unwrap_or_default())
+        }
+
+        /// Blocks everything sent to "blocked" and tracks the rest
+        pub fn sudo(
+            deps: DepsMut<TokenFactoryQuery>,
+            _env: Env,
+            msg: BeforeSendHookSudoMsg,
+        ) -> StdResult<Response<TokenFactoryMsg>> {
+            match msg {
+                BeforeSendHookSudoMsg::BlockBeforeSend { to, .. } => {
+                    if to == "blocked" {
+                        return Err(StdError::generic_err("recipient is blocked"));
+                    }
+                }
+                BeforeSendHookSudoMsg::TrackBeforeSend { from, to, amount } => {
+                    let mut transfers = TRANSFERS.may_load(deps.storage)?.unwrap_or_default();
+                    transfers.push((from, to, amount.amount.u128()));
+                    TRANSFERS.save(deps.storage, &transfers)?;
+                }
+            }
+            Ok(Response::new())
+        }
+    }
+
+    #[test]
+    fn before_send_hook_is_called() {
+        use cw_multi_test::ContractWrapper;
+
+        let creator = Addr::unchecked("govner");
+        let alice = Addr::unchecked("alice");
+        let denom = "factory/govner/fundz";
+        let mut app = TokenFactoryApp::new();
+
+        let code = ContractWrapper::new(
+            hook_contract::execute,
+            hook_contract::instantiate,
+            hook_contract::query,
+        )
+        .with_sudo(hook_contract::sudo);
+        let code_id = app.store_code(Box::new(code));
+        let hook = app
+            .instantiate_contract(code_id, creator.clone(), &Empty {}, &[], "hook", None)
+            .unwrap();
+
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        app.execute(creator.clone(), create.into()).unwrap();
+        let set_hook = TokenFactoryMsg::set_before_send_hook(denom, hook.to_string());
+        app.execute(creator.clone(), set_hook.into()).unwrap();
+
+        // mint, send between users, force transfer and burn all go through the hook
+        let mint =
+            TokenFactoryMsg::mint_contract_tokens(denom, Uint128::new(1000), alice.to_string());
+        app.execute(creator.clone(), mint.into()).unwrap();
+        app.send_tokens(alice.clone(), Addr::unchecked("bob"), &coins(100, denom))
+            .unwrap();
+        let force = TokenFactoryMsg::force_transfer_tokens(
+            denom,
+            Uint128::new(200),
+            alice.to_string(),
+            "carl".to_string(),
+        );
+        app.execute(creator.clone(), force.into()).unwrap();
+        let burn =
+            TokenFactoryMsg::burn_contract_tokens(denom, Uint128::new(300), alice.to_string());
+        app.execute(creator.clone(), burn.into()).unwrap();
+
+        let transfers: Vec<(String, String, u128)> =
+            app.wrap().query_wasm_smart(&hook, &Empty {}).unwrap();
+        assert_eq!(
+            transfers,
+            vec![
+                ("tokenfactory".to_string(), "alice".to_string(), 1000),
+                ("alice".to_string(), "bob".to_string(), 100),
+                ("alice".to_string(), "carl".to_string(), 200),
+                ("alice".to_string(), "tokenfactory".to_string(), 300),
+            ]
+        );
+
+        // blocked transfers are aborted, whichever way they happen
+        let blocked = Addr::unchecked("blocked");
+        app.send_tokens(alice.clone(), blocked.clone(), &coins(100, denom))
+            .unwrap_err();
+        let mint =
+            TokenFactoryMsg::mint_contract_tokens(denom, Uint128::new(1000), blocked.to_string());
+        app.execute(creator.clone(), mint.into()).unwrap_err();
+        let force = TokenFactoryMsg::force_transfer_tokens(
+            denom,
+            Uint128::new(100),
+            alice.to_string(),
+            blocked.to_string(),
+        );
+        let err = app.execute(creator.clone(), force.into()).unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("recipient is blocked"));
+
+        let balance = app.wrap().query_balance(blocked.as_str(), denom).unwrap();
+        assert_eq!(balance.amount, Uint128::zero());
+        let balance = app.wrap().query_balance(alice.as_str(), denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(400));
+
+        // other denoms are not hooked
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &alice, coins(100, "uosmo"))
+                .unwrap()
+        });
+        app.send_tokens(alice, blocked, &coins(100, "uosmo"))
+            .unwrap();
+    }
+
+    #[test]
+    fn burn_and_force_transfer_require_admin() {
+        let creator = Addr::unchecked("govner");
+        let intruder = Addr::unchecked("intruder");
+        let alice = Addr::unchecked("alice");
+        let denom = "factory/govner/fundz";
+        let mut app = TokenFactoryApp::new();
+
+        let burn =
+            TokenFactoryMsg::burn_contract_tokens(denom, Uint128::new(100), alice.to_string());
+        let force = TokenFactoryMsg::force_transfer_tokens(
+            denom,
+            Uint128::new(100),
+            alice.to_string(),
+            intruder.to_string(),
+        );
+
+        // denom doesn't exist yet
+        for msg in [burn.clone(), force.clone()] {
+            let err = app.execute(creator.clone(), msg.into()).unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::TokenDoesntExist
+            );
+        }
+
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        app.execute(creator.clone(), create.into()).unwrap();
+        let mint =
+            TokenFactoryMsg::mint_contract_tokens(denom, Uint128::new(1000), alice.to_string());
+        app.execute(creator.clone(), mint.into()).unwrap();
+
+        // only the admin may burn or move tokens
+        for msg in [burn.clone(), force.clone()] {
+            let err = app.execute(intruder.clone(), msg.into()).unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::NotTokenAdmin
+            );
+        }
+        let balance = app.wrap().query_balance(alice.as_str(), denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(1000));
+
+        app.execute(creator.clone(), burn.into()).unwrap();
+        app.execute(creator.clone(), force.into()).unwrap();
+        let balance = app.wrap().query_balance(alice.as_str(), denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(800));
+
+        // non factory denoms cannot be force transferred
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &alice, coins(100, "uosmo"))
+                .unwrap()
+        });
+        let force = TokenFactoryMsg::force_transfer_tokens(
+            "uosmo",
+            Uint128::new(100),
+            alice.to_string(),
+            creator.to_string(),
+        );
+        let err = app.execute(creator, force.into()).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::TokenDoesntExist
+        );
+    }
+
+    #[test]
+    fn empty_address_is_the_sender() {
+        let creator = Addr::unchecked("creator");
+        let denom = "factory/creator/fundz";
+        let mut app = TokenFactoryApp::new();
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        app.execute(creator.clone(), create.into()).unwrap();
+
+        let mint = TokenFactoryMsg::mint_contract_tokens(denom, Uint128::new(100), String::new());
+        let burn = TokenFactoryMsg::burn_contract_tokens(denom, Uint128::new(30), String::new());
+        // the custom and the stargate message behave the same
+        let paths: [fn(TokenFactoryMsg) -> CosmosMsg<TokenFactoryMsg>; 2] = [
+            |msg| msg.into(),
+            |msg| msg.to_stargate_msgs("creator").remove(0),
+        ];
+        for (i, path) in paths.into_iter().enumerate() {
+            let res = app.execute(creator.clone(), path(mint.clone())).unwrap();
+            TokenFactoryApp::assert_event(&res, EVENT_MINT, &[(ATTR_MINT_TO_ADDRESS, "creator")]);
+            let res = app.execute(creator.clone(), path(burn.clone())).unwrap();
+            TokenFactoryApp::assert_event(
+                &res,
+                EVENT_BURN,
+                &[
+                    (ATTR_BURN_FROM_ADDRESS, "creator"),
+                    (ATTR_AMOUNT, "30factory/creator/fundz"),
+                ],
+            );
+            let balance = app.wrap().query_balance(&creator, denom).unwrap();
+            assert_eq!(balance.amount, Uint128::new(70 * (i as u128 + 1)));
+        }
+    }
+
+    fn app_with_fee(fee: Vec<Coin>) -> TokenFactoryApp {
+        TokenFactoryAppBuilder::new()
+            .with_denom_creation_fee(fee)
+            .with_fee_collector(Addr::unchecked("community_pool"))
+            .build()
+    }
+
+    #[test]
+    fn create_denom_charges_fee() {
+        let creator = Addr::unchecked("govner");
+        let fee = vec![coin(100, "uatom"), coin(1000, "uosmo")];
+        let mut app = app_with_fee(fee.clone());
+        let fee_collector = app.fee_collector();
+        assert_eq!(fee_collector, Addr::unchecked("community_pool"));
+
+        let params = TokenQuerier::new(&app.wrap()).params().unwrap().params;
+        assert_eq!(params.denom_creation_fee, fee);
+
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+
+        // not enough of the second fee coin
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &creator,
+                    vec![coin(250, "uatom"), coin(999, "uosmo")],
+                )
+                .unwrap()
+        });
+        let err = app
+            .execute(creator.clone(), create.clone().into())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InsufficientCreationFee {
+                required: coin(1000, "uosmo"),
+                available: coin(999, "uosmo"),
+            }
+        );
+
+        // with enough funds the fee goes to the fee collector
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &creator,
+                    vec![coin(250, "uatom"), coin(1500, "uosmo")],
+                )
+                .unwrap()
+        });
+        app.execute(creator.clone(), create.into()).unwrap();
+        let balances = app.wrap().query_all_balances(creator.as_str()).unwrap();
+        assert_eq!(balances, vec![coin(150, "uatom"), coin(500, "uosmo")]);
+        let collected = app
+            .wrap()
+            .query_all_balances(fee_collector.as_str())
+            .unwrap();
+        assert_eq!(collected, fee);
+    }
+
+    #[test]
+    fn emits_chain_events() {
+        let creator = Addr::unchecked("govner");
+        let denom = "factory/govner/fundz";
+        let mut app = TokenFactoryApp::new();
+
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        let res = app.execute(creator.clone(), create.into()).unwrap();
+        TokenFactoryApp::assert_event(
+            &res,
+            EVENT_CREATE_DENOM,
+            &[(ATTR_CREATOR, "govner"), (ATTR_NEW_TOKEN_DENOM, denom)],
+        );
+
+        let mint = TokenFactoryMsg::mint_contract_tokens(denom, Uint128::new(500), "alice".into());
+        let res = app.execute(creator.clone(), mint.into()).unwrap();
+        TokenFactoryApp::assert_event(
+            &res,
+            EVENT_MINT,
+            &[
+                (ATTR_MINT_TO_ADDRESS, "alice"),
+                (ATTR_AMOUNT, "500factory/govner/fundz"),
+            ],
+        );
+
+        let burn = TokenFactoryMsg::burn_contract_tokens(denom, Uint128::new(100), "alice".into());
+        let res = app.execute(creator.clone(), burn.into()).unwrap();
+        TokenFactoryApp::assert_event(
+            &res,
+            EVENT_BURN,
+            &[
+                (ATTR_BURN_FROM_ADDRESS, "alice"),
+                (ATTR_AMOUNT, "100factory/govner/fundz"),
+            ],
+        );
+
+        let force = TokenFactoryMsg::force_transfer_tokens(
+            denom,
+            Uint128::new(100),
+            "alice".into(),
+            "bob".into(),
+        );
+        let res = app.execute(creator.clone(), force.into()).unwrap();
+        let event = TokenFactoryApp::find_event(&res, EVENT_FORCE_TRANSFER).unwrap();
+        assert_eq!(
+            TokenFactoryApp::event_attribute(event, ATTR_TRANSFER_FROM_ADDRESS),
+            Some("alice")
+        );
+        assert_eq!(
+            TokenFactoryApp::event_attribute(event, ATTR_TRANSFER_TO_ADDRESS),
+            Some("bob")
+        );
+
+        let metadata = fundz_metadata(denom);
+        let set_metadata = TokenFactoryMsg::set_metadata(denom, metadata.clone());
+        let res = app.execute(creator.clone(), set_metadata.into()).unwrap();
+        TokenFactoryApp::assert_event(
+            &res,
+            EVENT_SET_DENOM_METADATA,
+            &[
+                (ATTR_DENOM, denom),
+                (ATTR_DENOM_METADATA, &to_json_string(&metadata).unwrap()),
+            ],
+        );
+
+        let set_hook = TokenFactoryMsg::set_before_send_hook(denom, "hooker".into());
+        let res = app.execute(creator.clone(), set_hook.into()).unwrap();
+        TokenFactoryApp::assert_event(
+            &res,
+            EVENT_SET_BEFORE_SEND_HOOK,
+            &[
+                (ATTR_DENOM, denom),
+                (ATTR_BEFORE_SEND_HOOK_ADDRESS, "hooker"),
+            ],
+        );
+
+        let change_admin = TokenFactoryMsg::change_admin(denom, "newadmin".into());
+        let res = app.execute(creator, change_admin.into()).unwrap();
+        TokenFactoryApp::assert_event(
+            &res,
+            EVENT_CHANGE_ADMIN,
+            &[(ATTR_DENOM, denom), (ATTR_NEW_ADMIN, "newadmin")],
+        );
+        assert!(TokenFactoryApp::find_event(&res, EVENT_MINT).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "no tf_mint event")]
+    fn assert_event_fails_on_missing_event() {
+        TokenFactoryApp::assert_event(&AppResponse::default(), EVENT_MINT, &[]);
+    }
+
+    #[test]
+    fn governance_sudo() {
+        let creator = Addr::unchecked("govner");
+        let denom = "factory/govner/fundz";
+        let mut app = TokenFactoryApp::new();
+        let create = |subdenom: &str| {
+            TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+                subdenom: subdenom.to_string(),
+                metadata: None,
+            })
+        };
+        app.execute(creator.clone(), create("fundz").into())
+            .unwrap();
+
+        // update params
+        let params = Params {
+            denom_creation_fee: vec![coin(10, "uosmo")],
+            denom_creation_gas_consume: 0,
+        };
+        let res = app
+            .sudo_token_factory(TokenFactorySudo::UpdateParams {
+                params: params.clone(),
+            })
+            .unwrap();
+        assert!(res.events.is_empty());
+        let querier = app.wrap();
+        assert_eq!(TokenQuerier::new(&querier).params().unwrap().params, params);
+        let err = app
+            .execute(creator.clone(), create("other").into())
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            ContractError::InsufficientCreationFee { .. }
+        ));
+        app.sudo_token_factory(TokenFactorySudo::UpdateParams {
+            params: Params::default(),
+        })
+        .unwrap();
+
+        // force set admin
+        let res = app
+            .sudo_token_factory(TokenFactorySudo::ForceSetAdmin {
+                denom: denom.to_string(),
+                new_admin: "council".to_string(),
+            })
+            .unwrap();
+        TokenFactoryApp::assert_event(&res, EVENT_CHANGE_ADMIN, &[(ATTR_NEW_ADMIN, "council")]);
+        let querier = app.wrap();
+        assert_eq!(
+            TokenQuerier::new(&querier).admin(denom).unwrap().admin,
+            "council"
+        );
+        let err = app
+            .sudo_token_factory(TokenFactorySudo::ForceSetAdmin {
+                denom: "factory/govner/missing".to_string(),
+                new_admin: "council".to_string(),
+            })
+            .unwrap_err();
+        assert_eq!(ContractError::TokenDoesntExist, err.downcast().unwrap());
+
+        // governance can renounce the admin, like the admin itself
+        let renounced = "factory/govner/renounced";
+        app.execute(creator.clone(), create("renounced").into())
+            .unwrap();
+        let res = app
+            .sudo_token_factory(TokenFactorySudo::ForceSetAdmin {
+                denom: renounced.to_string(),
+                new_admin: String::new(),
+            })
+            .unwrap();
+        TokenFactoryApp::assert_event(&res, EVENT_CHANGE_ADMIN, &[(ATTR_NEW_ADMIN, "")]);
+        let querier = app.wrap();
+        assert_eq!(
+            TokenQuerier::new(&querier).admin(renounced).unwrap().admin,
+            ""
+        );
+
+        // force set metadata
+        let metadata = Metadata {
+            description: Some("set by governance".to_string()),
+            ..fundz_metadata(denom)
+        };
+        app.sudo_token_factory(TokenFactorySudo::ForceSetMetadata {
+            denom: denom.to_string(),
+            metadata: metadata.clone(),
+        })
+        .unwrap();
+        let querier = app.wrap();
+        assert_eq!(
+            TokenQuerier::new(&querier)
+                .metadata(denom)
+                .unwrap()
+                .metadata,
+            Some(metadata)
+        );
+
+        // disable creation, then enable it again
+        app.sudo_token_factory(TokenFactorySudo::DisableCreation { disabled: true })
+            .unwrap();
+        let err = app
+            .execute(creator.clone(), create("other").into())
+            .unwrap_err();
+        assert_eq!(ContractError::CreationDisabled, err.downcast().unwrap());
+        app.sudo_token_factory(TokenFactorySudo::DisableCreation { disabled: false })
+            .unwrap();
+        app.execute(creator, create("other").into()).unwrap();
+    }
+
+    #[test]
+    fn renounce_admin() {
+        let creator = Addr::unchecked("govner");
+        let denom = "factory/govner/fundz";
+        let mut app = TokenFactoryApp::new();
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        app.execute(creator.clone(), create.into()).unwrap();
+
+        let renounce = TokenFactoryMsg::renounce_admin(denom);
+        let res = app.execute(creator.clone(), renounce.into()).unwrap();
+        TokenFactoryApp::assert_event(&res, EVENT_CHANGE_ADMIN, &[(ATTR_NEW_ADMIN, "")]);
+
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert_eq!(querier.admin(denom).unwrap().admin, "");
+        assert_eq!(querier.try_admin(denom).unwrap(), None);
+        // the denom still exists
+        assert_eq!(
+            querier.all_denoms_page(None, None).unwrap().denoms,
+            vec![denom]
+        );
+
+        let admin_msgs = [
+            TokenFactoryMsg::mint_contract_tokens(denom, Uint128::new(1), "govner".into()),
+            TokenFactoryMsg::change_admin(denom, "govner".into()),
+            TokenFactoryMsg::change_admin(denom, String::new()),
+        ];
+        for msg in admin_msgs {
+            let err = app.execute(creator.clone(), msg.into()).unwrap_err();
+            assert_eq!(ContractError::NotTokenAdmin, err.downcast().unwrap());
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_metadata() {
+        let creator = Addr::unchecked("govner");
+        let denom = "factory/govner/fundz";
+        let mut app = TokenFactoryApp::new();
+
+        // on creation
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: Some(Metadata {
+                display: Some("MEGAFUNDZ".to_string()),
+                ..fundz_metadata(denom)
+            }),
+        });
+        let err = app.execute(creator.clone(), create.into()).unwrap_err();
+        assert_eq!(
+            ContractError::InvalidMetadata(MetadataError::DisplayNotInUnits {
+                display: "MEGAFUNDZ".to_string()
+            }),
+            err.downcast().unwrap()
+        );
+        // nothing was created
+        let querier = app.wrap();
+        assert_eq!(TokenQuerier::new(&querier).admin(denom).unwrap().admin, "");
+
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: Some(fundz_metadata(denom)),
+        });
+        app.execute(creator.clone(), create.into()).unwrap();
+
+        // and when set later
+        let other = "factory/govner/other";
+        let set_metadata = TokenFactoryMsg::set_metadata(denom, fundz_metadata(other));
+        let err = app.execute(creator, set_metadata.into()).unwrap_err();
+        assert_eq!(
+            ContractError::InvalidMetadata(MetadataError::BaseMismatch {
+                base: other.to_string(),
+                denom: denom.to_string()
+            }),
+            err.downcast().unwrap()
+        );
+        let querier = app.wrap();
+        assert_eq!(
+            TokenQuerier::new(&querier)
+                .metadata(denom)
+                .unwrap()
+                .metadata,
+            Some(fundz_metadata(denom))
+        );
+    }
+
+    #[test]
+    fn validates_uri_hash() {
+        let creator = Addr::unchecked("govner");
+        let denom = "factory/govner/fundz";
+        let mut app = TokenFactoryApp::new();
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        app.execute(creator.clone(), create.into()).unwrap();
+
+        let uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+        for uri_hash in ["abc", &"g".repeat(64), &"ab".repeat(33)] {
+            let metadata = Metadata {
+                uri: Some(uri.to_string()),
+                uri_hash: Some(uri_hash.to_string()),
+                ..fundz_metadata(denom)
+            };
+            let set_metadata = TokenFactoryMsg::set_metadata(denom, metadata);
+            let err = app
+                .execute(creator.clone(), set_metadata.into())
+                .unwrap_err();
+            assert_eq!(
+                ContractError::InvalidUriHash {
+                    uri_hash: uri_hash.to_string()
+                },
+                err.downcast().unwrap()
+            );
+        }
+
+        let uri_hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852B855";
+        let metadata = Metadata {
+            uri: Some(uri.to_string()),
+            uri_hash: Some(uri_hash.to_string()),
+            ..fundz_metadata(denom)
+        };
+        let set_metadata = TokenFactoryMsg::set_metadata(denom, metadata.clone());
+        app.execute(creator, set_metadata.into()).unwrap();
+        let querier = app.wrap();
+        assert_eq!(
+            TokenQuerier::new(&querier)
+                .metadata(denom)
+                .unwrap()
+                .metadata,
+            Some(metadata)
+        );
+    }
+
+    #[test]
+    fn validates_denoms_like_the_bindings() {
+        let creator = Addr::unchecked("govner");
+        let mut app = TokenFactoryApp::new();
+        let long_subdenom = "s".repeat(45);
+        let subdenoms = [
+            "fundz",
+            "",
+            "fun/dz",
+            "fun:dz.v2_a-b",
+            "fun dz",
+            "fündz",
+            "fun+dz",
+            long_subdenom.as_str(),
+        ];
+        for subdenom in subdenoms {
+            let expected = FactoryDenom::new(creator.as_str(), subdenom);
+
+            let query = TokenFactoryQuery::Token(TokenFactoryQueryEnum::FullDenom {
+                creator_addr: creator.to_string(),
+                subdenom: subdenom.to_string(),
+            });
+            let res = app.wrap().query::<FullDenomResponse>(&query.into());
+            let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+                subdenom: subdenom.to_string(),
+                metadata: None,
+            });
+            let created = app.execute(creator.clone(), create.into());
+            match expected {
+                Ok(denom) => {
+                    assert_eq!(res.unwrap().denom, denom.to_string());
+                    created.unwrap();
+                }
+                Err(err) => {
+                    assert!(res.unwrap_err().to_string().contains(&err.to_string()));
+                    assert_eq!(
+                        created.unwrap_err().downcast::<ContractError>().unwrap(),
+                        ContractError::InvalidFullDenom(err),
+                        "{}",
+                        subdenom
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn consumes_creation_gas() {
+        let creator = Addr::unchecked("govner");
+        let mut app = TokenFactoryAppBuilder::new()
+            .with_denom_creation_gas_consume(2_000_000)
+            .build();
+        let create = |subdenom: &str| {
+            TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+                subdenom: subdenom.to_string(),
+                metadata: None,
+            })
+        };
+
+        let querier = app.wrap();
+        let params = TokenQuerier::new(&querier).params().unwrap().params;
+        assert_eq!(params.denom_creation_gas_consume, 2_000_000);
+        assert_eq!(app.denom_creation_gas_consumed(), 0);
+
+        app.execute(creator.clone(), create("fundz").into())
+            .unwrap();
+        assert_eq!(app.denom_creation_gas_consumed(), 2_000_000);
+
+        // failed creations consume nothing
+        app.execute(creator.clone(), create("fundz").into())
+            .unwrap_err();
+        assert_eq!(app.denom_creation_gas_consumed(), 2_000_000);
+
+        // governance may change the amount
+        app.sudo_token_factory(TokenFactorySudo::UpdateParams {
+            params: Params {
+                denom_creation_fee: vec![],
+                denom_creation_gas_consume: 500_000,
+            },
+        })
+        .unwrap();
+        app.execute(creator, create("other").into()).unwrap();
+        assert_eq!(app.denom_creation_gas_consumed(), 2_500_000);
+    }
+
+    #[test]
+    #[cfg(feature = "cosmwasm_1_3")]
+    fn bank_denom_metadata() {
+        use cosmwasm_std::PageRequest;
+
+        let creator = Addr::unchecked("govner");
+        let genesis = format!("factory/{}/gen", creator);
+        let denom = format!("factory/{}/fundz", creator);
+        let mut app = TokenFactoryAppBuilder::new()
+            .with_denom(
+                creator.clone(),
+                "gen",
+                creator.clone(),
+                Some(fundz_metadata(&genesis)),
+            )
+            .build();
+
+        // no metadata yet
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        app.execute(creator.clone(), create.into()).unwrap();
+        let bank = app.wrap().query_denom_metadata(&denom).unwrap();
+        assert_eq!(bank.base, "");
+
+        let metadata = Metadata::builder(&denom)
+            .alias(&denom, "microfundz")
+            .display("FUNDZ", 6)
+            .description("Fundz")
+            .build()
+            .unwrap();
+        let set = TokenFactoryMsg::set_metadata(&denom, metadata);
+        app.execute(creator.clone(), set.into()).unwrap();
+        let bank = app.wrap().query_denom_metadata(&denom).unwrap();
+        assert_eq!(bank.base, denom);
+        assert_eq!(bank.display, "FUNDZ");
+        assert_eq!(bank.description, "Fundz");
+        assert_eq!(bank.uri, "");
+        assert_eq!(bank.denom_units[0].aliases, vec!["microfundz".to_string()]);
+        assert_eq!(bank.denom_units[1].exponent, 6);
+
+        // governance updates both as well
+        app.sudo_token_factory(TokenFactorySudo::ForceSetMetadata {
+            denom: denom.clone(),
+            metadata: fundz_metadata(&denom),
+        })
+        .unwrap();
+
+        // both query paths agree on every denom
+        let all = app
+            .wrap()
+            .query_all_denom_metadata(PageRequest {
+                key: None,
+                limit: 10,
+                reverse: false,
+            })
+            .unwrap()
+            .metadata;
+        assert_eq!(all.len(), 2);
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        for bank in all {
+            let metadata = querier.metadata(&bank.base).unwrap().metadata.unwrap();
+            assert_eq!(Some(bank.name), metadata.name);
+            assert_eq!(Some(bank.symbol), metadata.symbol);
+            assert_eq!(bank.denom_units.len(), metadata.denom_units.len());
+        }
+    }
+
+    #[test]
+    fn supply_invariants_hold() {
+        let creator = Addr::unchecked("govner");
+        let holder = Addr::unchecked("holder");
+        let denom = format!("factory/{}/fundz", creator);
+        let mut app = TokenFactoryAppBuilder::new()
+            .with_denom(creator.clone(), "fundz", creator.clone(), None)
+            .with_balance(holder.clone(), vec![coin(50, &denom), coin(7, "uosmo")])
+            .with_invariant_checks(true)
+            .build();
+
+        let msgs: Vec<CosmosMsg<TokenFactoryMsg>> = vec![
+            TokenFactoryMsg::mint_contract_tokens(&denom, Uint128::new(100), holder.to_string())
+                .into(),
+            TokenFactoryMsg::burn_contract_tokens(&denom, Uint128::new(30), holder.to_string())
+                .into(),
+            TokenFactoryMsg::force_transfer_tokens(
+                &denom,
+                Uint128::new(20),
+                holder.to_string(),
+                creator.to_string(),
+            )
+            .into(),
+            TokenFactoryMsg::set_metadata(&denom, fundz_metadata(&denom)).into(),
+        ];
+        app.execute_multi(creator.clone(), msgs).unwrap();
+
+        // burning directly via the bank lowers the supply as well
+        let burn = BankMsg::Burn {
+            amount: coins(5, &denom),
+        };
+        app.execute(holder.clone(), burn.into()).unwrap();
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert_eq!(
+            querier.total_supply(&denom).unwrap().amount,
+            coin(115, &denom)
+        );
+
+        // failed executions are checked as well
+        let burn = BankMsg::Burn {
+            amount: coins(1000, &denom),
+        };
+        app.execute(holder, burn.into()).unwrap_err();
+        app.assert_token_factory_invariants();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "total supply of factory/govner/fundz is 0, but its balances add up to 10"
+    )]
+    fn checks_invariants_after_execute() {
+        let creator = Addr::unchecked("govner");
+        let mut app = TokenFactoryAppBuilder::new()
+            .with_denom(creator.clone(), "fundz", creator.clone(), None)
+            .with_balance(creator.clone(), coins(10, "factory/govner/fundz"))
+            .with_invariant_checks(true)
+            .build();
+        // corrupted without executing anything, so it goes unnoticed until the next execution
+        app.init_modules(|_, _, storage| SUPPLY.remove(storage, "factory/govner/fundz"));
+        let send = BankMsg::Send {
+            to_address: "holder".to_string(),
+            amount: coins(1, "factory/govner/fundz"),
+        };
+        let _ = app.execute(creator, send.into());
+    }
+
+    #[test]
+    fn reports_invariant_violations() {
+        let creator = Addr::unchecked("govner");
+        let app = TokenFactoryAppBuilder::new()
+            .with_denom(creator.clone(), "fundz", creator.clone(), None)
+            .with_balance(creator.clone(), coins(10, "factory/govner/fundz"))
+            .build();
+        app.assert_token_factory_invariants();
+
+        let mut app = app;
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &creator, coins(3, "factory/govner/ghost"))
+                .unwrap();
+            // not a factory denom, despite the name
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("other"), coins(5, "factorytoken"))
+                .unwrap();
+            DENOMS_BY_CREATOR.remove(storage, (&creator, "factory/govner/fundz"));
+            DENOMS_BY_CREATOR
+                .save(
+                    storage,
+                    (&Addr::unchecked("other"), "factory/govner/fundz"),
+                    &Empty {},
+                )
+                .unwrap();
+            METADATA
+                .save(
+                    storage,
+                    "factory/govner/gone",
+                    &fundz_metadata("factory/govner/gone"),
+                )
+                .unwrap();
+        });
+        let balances = app.balances_by_denom().unwrap();
+        let violations = app
+            .read_module(|router, _, storage| {
+                router.custom.invariant_violations(storage, &balances)
+            })
+            .unwrap();
+        assert_eq!(
+            violations,
+            vec![
+                "factory/govner/fundz is not listed under its creator govner",
+                // the genesis balance was replaced
+                "total supply of factory/govner/fundz is 10, but its balances add up to 0",
+                "factory/govner/ghost has balances, but was never created",
+                "factory/govner/fundz is listed under other, who did not create it",
+                "factory/govner/gone has a metadata, but does not exist",
+            ]
+        );
+    }
 }
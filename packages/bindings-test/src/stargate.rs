@@ -0,0 +1,683 @@
+//! Handles the token factory messages and queries of contracts that talk to the chain
+//! via `CosmosMsg::Stargate` and `QueryRequest::Stargate` instead of the custom bindings.
+//! Messages are decoded and run by the same logic as `TokenFactoryModule::execute`.
+//!
+//! Like chains with a stargate query accept list, queries take protobuf encoded
+//! requests and answer in JSON.
+
+use anyhow::{anyhow, bail, Result as AnyResult};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Api, Binary, BlockInfo, Coin, CustomMsg, CustomQuery, Empty,
+    Querier, StdError, StdResult, Storage, Uint128, Uint64,
+};
+use cw_multi_test::{AppResponse, CosmosRouter, Module, Stargate, StargateMsg, StargateQuery};
+
+use token_bindings::stargate::{
+    MSG_BURN, MSG_CHANGE_ADMIN, MSG_CREATE_DENOM, MSG_FORCE_TRANSFER, MSG_MINT,
+    MSG_SET_BEFORE_SEND_HOOK, MSG_SET_DENOM_METADATA, OSMOSIS_TOKENFACTORY_PACKAGE,
+};
+use token_bindings::{
+    AdminResponse, DenomUnit, DenomsByCreatorResponse, Metadata, Params, ParamsResponse,
+    TokenFactoryMsg, TokenFactoryMsgOptions, TokenFactoryQuery, TokenFactoryQueryEnum,
+};
+
+use crate::multitest::{TokenFactoryModule, MAX_DENOMS_LIMIT};
+
+pub const QUERY_PARAMS: &str = "Params";
+pub const QUERY_DENOM_AUTHORITY_METADATA: &str = "DenomAuthorityMetadata";
+pub const QUERY_DENOMS_FROM_CREATOR: &str = "DenomsFromCreator";
+
+/// Path of a query of the given protobuf package, e.g.
+/// /osmosis.tokenfactory.v1beta1.Query/Params
+pub fn query_path(package: &str, query: &str) -> String {
+    format!("/{}.Query/{}", package, query)
+}
+
+/// Stargate module of `TokenFactoryApp`, sharing the state of its `TokenFactoryModule`
+pub struct TokenFactoryStargate {
+    module: TokenFactoryModule,
+    package: String,
+}
+
+impl TokenFactoryStargate {
+    /// Handles the messages of `OSMOSIS_TOKENFACTORY_PACKAGE` with the given module,
+    /// which must be the custom module of the app
+    pub fn new(module: TokenFactoryModule) -> Self {
+        Self::with_package(module, OSMOSIS_TOKENFACTORY_PACKAGE)
+    }
+
+    /// Like `new`, for chains that use another protobuf package for the same messages
+    pub fn with_package(module: TokenFactoryModule, package: impl Into<String>) -> Self {
+        TokenFactoryStargate {
+            module,
+            package: package.into(),
+        }
+    }
+
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    /// Decodes a token factory message into its signer and the equivalent binding
+    fn decode_msg(&self, type_url: &str, value: &[u8]) -> AnyResult<(String, TokenFactoryMsg)> {
+        let name = type_url
+            .strip_prefix('/')
+            .and_then(|url| url.strip_prefix(self.package.as_str()))
+            .and_then(|url| url.strip_prefix('.'))
+            .ok_or_else(|| anyhow!("Unsupported stargate message: {}", type_url))?;
+        let msg = ProtoMessage::decode(value)?;
+        let sender = msg.string(1)?;
+        // mints and burns without address are for the sender, which the module takes care of
+        let options = match name {
+            MSG_CREATE_DENOM => TokenFactoryMsgOptions::CreateDenom {
+                subdenom: msg.string(2)?,
+                metadata: None,
+            },
+            MSG_MINT => {
+                let (denom, amount) = decode_coin(msg.message(2)?)?;
+                TokenFactoryMsgOptions::MintTokens {
+                    denom,
+                    amount,
+                    mint_to_address: msg.string(3)?,
+                }
+            }
+            MSG_BURN => {
+                let (denom, amount) = decode_coin(msg.message(2)?)?;
+                TokenFactoryMsgOptions::BurnTokens {
+                    denom,
+                    amount,
+                    burn_from_address: msg.string(3)?,
+                }
+            }
+            MSG_CHANGE_ADMIN => TokenFactoryMsgOptions::ChangeAdmin {
+                denom: msg.string(2)?,
+                new_admin_address: msg.string(3)?,
+            },
+            MSG_SET_DENOM_METADATA => {
+                let metadata = msg
+                    .message(2)?
+                    .ok_or_else(|| anyhow!("Missing metadata in {}", type_url))?;
+                let metadata = decode_metadata(&metadata)?;
+                TokenFactoryMsgOptions::SetMetadata {
+                    denom: metadata.base.clone().unwrap_or_default(),
+                    metadata,
+                }
+            }
+            MSG_SET_BEFORE_SEND_HOOK => TokenFactoryMsgOptions::SetBeforeSendHook {
+                denom: msg.string(2)?,
+                contract_addr: msg.string(3)?,
+            },
+            MSG_FORCE_TRANSFER => {
+                let (denom, amount) = decode_coin(msg.message(2)?)?;
+                TokenFactoryMsgOptions::ForceTransfer {
+                    denom,
+                    amount,
+                    from_address: msg.string(3)?,
+                    to_address: msg.string(4)?,
+                }
+            }
+            _ => bail!("Unsupported stargate message: {}", type_url),
+        };
+        Ok((sender, TokenFactoryMsg::Token(options)))
+    }
+}
+
+/// JSON of osmosis.tokenfactory.v1beta1.QueryParamsResponse
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StargateParamsResponse {
+    pub params: StargateParams,
+}
+
+/// Like `Params`, with the uint64 as a string as in the protobuf JSON mapping.
+/// This is also how they appear in the chain's genesis.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StargateParams {
+    pub denom_creation_fee: Vec<Coin>,
+    pub denom_creation_gas_consume: Uint64,
+}
+
+impl From<Params> for StargateParams {
+    fn from(params: Params) -> Self {
+        StargateParams {
+            denom_creation_fee: params.denom_creation_fee,
+            denom_creation_gas_consume: params.denom_creation_gas_consume.into(),
+        }
+    }
+}
+
+impl From<StargateParams> for Params {
+    fn from(params: StargateParams) -> Self {
+        Params {
+            denom_creation_fee: params.denom_creation_fee,
+            denom_creation_gas_consume: params.denom_creation_gas_consume.u64(),
+        }
+    }
+}
+
+/// JSON of osmosis.tokenfactory.v1beta1.QueryDenomAuthorityMetadataResponse
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StargateDenomAuthorityMetadataResponse {
+    pub authority_metadata: StargateDenomAuthorityMetadata,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StargateDenomAuthorityMetadata {
+    pub admin: String,
+}
+
+/// JSON of osmosis.tokenfactory.v1beta1.QueryDenomsFromCreatorResponse
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StargateDenomsFromCreatorResponse {
+    pub denoms: Vec<String>,
+}
+
+impl Module for TokenFactoryStargate {
+    type ExecT = StargateMsg;
+    type QueryT = StargateQuery;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: Self::ExecT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + CustomMsg + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        let (signer, msg) = self.decode_msg(&msg.type_url, &msg.value)?;
+        // the chain only executes messages signed by the contract
+        if signer != sender.as_str() {
+            bail!(
+                "Stargate message signed by {} cannot be sent by {}",
+                signer,
+                sender
+            );
+        }
+        self.module
+            .execute(api, storage, router, block, sender, msg)
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Self::SudoT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + CustomMsg + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        bail!("Stargate sudo is not supported")
+    }
+
+    fn query(
+        &self,
+        api: &dyn Api,
+        storage: &dyn Storage,
+        querier: &dyn Querier,
+        block: &BlockInfo,
+        request: Self::QueryT,
+    ) -> AnyResult<Binary> {
+        let query = |query: TokenFactoryQueryEnum| {
+            self.module.query(
+                api,
+                storage,
+                querier,
+                block,
+                TokenFactoryQuery::Token(query),
+            )
+        };
+        let name = request
+            .path
+            .strip_prefix(&format!("/{}.Query/", self.package))
+            .ok_or_else(|| anyhow!("Unsupported stargate query: {}", request.path))?;
+        let data = ProtoMessage::decode(&request.data)?;
+        match name {
+            QUERY_PARAMS => {
+                let ParamsResponse { params } =
+                    from_json(query(TokenFactoryQueryEnum::Params {})?)?;
+                Ok(to_json_binary(&StargateParamsResponse {
+                    params: params.into(),
+                })?)
+            }
+            QUERY_DENOM_AUTHORITY_METADATA => {
+                let denom = data.string(1)?;
+                let AdminResponse { admin } =
+                    from_json(query(TokenFactoryQueryEnum::Admin { denom })?)?;
+                Ok(to_json_binary(&StargateDenomAuthorityMetadataResponse {
+                    authority_metadata: StargateDenomAuthorityMetadata { admin },
+                })?)
+            }
+            QUERY_DENOMS_FROM_CREATOR => {
+                // the chain returns all denoms at once
+                let creator = data.string(1)?;
+                let mut denoms = vec![];
+                let mut start_after = None;
+                loop {
+                    let page: DenomsByCreatorResponse =
+                        from_json(query(TokenFactoryQueryEnum::DenomsByCreator {
+                            creator: creator.clone(),
+                            start_after,
+                            limit: Some(MAX_DENOMS_LIMIT),
+                        })?)?;
+                    denoms.extend(page.denoms);
+                    start_after = page.next_start_after;
+                    if start_after.is_none() {
+                        break;
+                    }
+                }
+                Ok(to_json_binary(&StargateDenomsFromCreatorResponse {
+                    denoms,
+                })?)
+            }
+            _ => bail!("Unsupported stargate query: {}", request.path),
+        }
+    }
+}
+
+impl Stargate for TokenFactoryStargate {}
+
+/// cosmos.base.v1beta1.Coin, which the chain requires to be set
+fn decode_coin(coin: Option<ProtoMessage>) -> AnyResult<(String, Uint128)> {
+    let coin = coin.ok_or_else(|| anyhow!("Missing amount"))?;
+    let amount = match coin.string(2)?.as_str() {
+        "" => Uint128::zero(),
+        amount => Uint128::from_str(amount)?,
+    };
+    Ok((coin.string(1)?, amount))
+}
+
+/// cosmos.bank.v1beta1.Metadata, with empty strings as None
+fn decode_metadata(metadata: &ProtoMessage) -> StdResult<Metadata> {
+    let optional = |field_number| {
+        metadata
+            .string(field_number)
+            .map(|value| Some(value).filter(|value| !value.is_empty()))
+    };
+    let denom_units = metadata
+        .messages(2)?
+        .iter()
+        .map(|unit| {
+            Ok(DenomUnit {
+                denom: unit.string(1)?,
+                exponent: u32::try_from(unit.uint(2)?)
+                    .map_err(|_| StdError::generic_err("Exponent out of range"))?,
+                aliases: unit.strings(3)?,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(Metadata {
+        description: optional(1)?,
+        denom_units,
+        base: optional(3)?,
+        display: optional(4)?,
+        name: optional(5)?,
+        symbol: optional(6)?,
+        uri: optional(7)?,
+        uri_hash: optional(8)?,
+    })
+}
+
+// Protobuf wire types (https://developers.google.com/protocol-buffers/docs/encoding)
+const WIRE_TYPE_VARINT: u64 = 0;
+const WIRE_TYPE_LENGTH_DELIMITED: u64 = 2;
+// Varints of uint64 values take up to 10 bytes
+const VARINT_MAX_BYTES: usize = 10;
+
+enum ProtoValue {
+    Varint(u64),
+    LengthDelimited(Vec<u8>),
+}
+
+/// The fields of a protobuf message, decoded without knowing its schema
+struct ProtoMessage {
+    fields: Vec<(u64, ProtoValue)>,
+}
+
+impl ProtoMessage {
+    fn decode(mut data: &[u8]) -> StdResult<Self> {
+        let mut fields = vec![];
+        while !data.is_empty() {
+            let key = decode_varint(&mut data)?;
+            let value = match key & 0b111 {
+                WIRE_TYPE_VARINT => ProtoValue::Varint(decode_varint(&mut data)?),
+                WIRE_TYPE_LENGTH_DELIMITED => {
+                    let len = decode_varint(&mut data)? as usize;
+                    if data.len() < len {
+                        return Err(decode_err("message too short"));
+                    }
+                    let (value, rest) = data.split_at(len);
+                    data = rest;
+                    ProtoValue::LengthDelimited(value.to_vec())
+                }
+                wire_type => {
+                    return Err(decode_err(&format!("unsupported wire type {}", wire_type)))
+                }
+            };
+            fields.push((key >> 3, value));
+        }
+        Ok(ProtoMessage { fields })
+    }
+
+    /// All values of a length delimited field, in order
+    fn bytes(&self, field_number: u64) -> StdResult<Vec<&[u8]>> {
+        self.values(field_number)
+            .map(|value| match value {
+                ProtoValue::LengthDelimited(bytes) => Ok(bytes.as_slice()),
+                ProtoValue::Varint(_) => Err(wire_type_err(field_number)),
+            })
+            .collect()
+    }
+
+    /// The last value of a string field, or the empty default
+    fn string(&self, field_number: u64) -> StdResult<String> {
+        Ok(self.strings(field_number)?.pop().unwrap_or_default())
+    }
+
+    fn strings(&self, field_number: u64) -> StdResult<Vec<String>> {
+        self.bytes(field_number)?
+            .into_iter()
+            .map(|bytes| Ok(String::from_utf8(bytes.to_vec())?))
+            .collect()
+    }
+
+    /// The last value of an embedded message field, if set
+    fn message(&self, field_number: u64) -> StdResult<Option<ProtoMessage>> {
+        Ok(self.messages(field_number)?.pop())
+    }
+
+    fn messages(&self, field_number: u64) -> StdResult<Vec<ProtoMessage>> {
+        self.bytes(field_number)?
+            .into_iter()
+            .map(ProtoMessage::decode)
+            .collect()
+    }
+
+    /// The last value of an unsigned integer field, or 0
+    fn uint(&self, field_number: u64) -> StdResult<u64> {
+        self.values(field_number)
+            .map(|value| match value {
+                ProtoValue::Varint(value) => Ok(*value),
+                ProtoValue::LengthDelimited(_) => Err(wire_type_err(field_number)),
+            })
+            .last()
+            .unwrap_or(Ok(0))
+    }
+
+    fn values(&self, field_number: u64) -> impl Iterator<Item = &ProtoValue> {
+        self.fields
+            .iter()
+            .filter(move |(number, _)| *number == field_number)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Base128 varint decoding.
+/// The remaining of the data is kept in the data parameter.
+fn decode_varint(data: &mut &[u8]) -> StdResult<u64> {
+    let mut value: u64 = 0;
+    for (i, byte) in data.iter().take(VARINT_MAX_BYTES).enumerate() {
+        value |= ((byte & 0x7f) as u64) << (i * 7);
+        if byte & 0x80 == 0 {
+            *data = &data[i + 1..];
+            return Ok(value);
+        }
+    }
+    Err(decode_err("invalid varint"))
+}
+
+fn decode_err(msg: &str) -> StdError {
+    StdError::parse_err(
+        "protobuf",
+        format!("failed to decode Protobuf message: {}", msg),
+    )
+}
+
+fn wire_type_err(field_number: u64) -> StdError {
+    decode_err(&format!("field #{}: invalid wire type", field_number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{coin, coins, CosmosMsg, QueryRequest};
+    use cw_multi_test::Executor;
+    use token_bindings::TokenQuerier;
+
+    use crate::error::ContractError;
+    use crate::{TokenFactoryApp, TokenFactoryAppBuilder};
+
+    /// Protobuf of a request with a single short string field
+    fn string_request(value: &str) -> Binary {
+        [&[0x0a, value.len() as u8], value.as_bytes()]
+            .concat()
+            .into()
+    }
+
+    fn query<T: DeserializeOwned>(app: &TokenFactoryApp, query: &str, data: Binary) -> T {
+        let path = query_path(OSMOSIS_TOKENFACTORY_PACKAGE, query);
+        app.wrap()
+            .query(&QueryRequest::Stargate { path, data })
+            .unwrap()
+    }
+
+    #[test]
+    fn executes_stargate_msgs() {
+        let contract = Addr::unchecked("contract");
+        let holder = Addr::unchecked("holder");
+        let denom = format!("factory/{}/fundz", contract);
+        let mut app = TokenFactoryApp::new();
+        let stargate =
+            |msg: TokenFactoryMsg| msg.to_stargate_msgs::<TokenFactoryMsg>(contract.as_str());
+
+        // metadata is set by a second message
+        let metadata = Metadata::builder(&denom)
+            .display("FUNDZ", 6)
+            .uri("https://fundz.io", "ab".repeat(32))
+            .build()
+            .unwrap();
+        let create = stargate(TokenFactoryMsg::Token(
+            TokenFactoryMsgOptions::CreateDenom {
+                subdenom: "fundz".to_string(),
+                metadata: Some(metadata.clone()),
+            },
+        ));
+        assert_eq!(create.len(), 2);
+        let res = app.execute_multi(contract.clone(), create).unwrap();
+        assert_eq!(
+            token_bindings::CreateDenomResponse::from_reply_data(res[0].data.clone().unwrap())
+                .unwrap()
+                .new_token_denom,
+            denom
+        );
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert_eq!(querier.metadata(&denom).unwrap().metadata, Some(metadata));
+
+        // mints and burns without address are for the sender
+        let mint = stargate(TokenFactoryMsg::mint_contract_tokens(
+            &denom,
+            Uint128::new(100),
+            holder.to_string(),
+        ));
+        let mint_self = stargate(TokenFactoryMsg::mint_contract_tokens(
+            &denom,
+            Uint128::new(50),
+            String::new(),
+        ));
+        let burn_self = stargate(TokenFactoryMsg::burn_contract_tokens(
+            &denom,
+            Uint128::new(20),
+            String::new(),
+        ));
+        let transfer = stargate(TokenFactoryMsg::force_transfer_tokens(
+            &denom,
+            Uint128::new(10),
+            holder.to_string(),
+            contract.to_string(),
+        ));
+        let msgs = [mint, mint_self, burn_self, transfer].concat();
+        app.execute_multi(contract.clone(), msgs).unwrap();
+        assert_eq!(
+            app.wrap().query_balance(&holder, &denom).unwrap(),
+            coin(90, &denom)
+        );
+        assert_eq!(
+            app.wrap().query_balance(&contract, &denom).unwrap(),
+            coin(40, &denom)
+        );
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert_eq!(
+            querier.total_supply(&denom).unwrap().amount,
+            coin(130, &denom)
+        );
+
+        // the module still checks the admin
+        let mint = stargate(TokenFactoryMsg::mint_contract_tokens(
+            "factory/other/fundz",
+            Uint128::new(1),
+            String::new(),
+        ));
+        let err = app.execute(contract.clone(), mint[0].clone()).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::TokenDoesntExist
+        );
+
+        let change = stargate(TokenFactoryMsg::change_admin(&denom, holder.to_string()));
+        app.execute(contract.clone(), change[0].clone()).unwrap();
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert_eq!(querier.admin(&denom).unwrap().admin, holder.to_string());
+    }
+
+    #[test]
+    fn rejects_foreign_and_unknown_msgs() {
+        let mut app = TokenFactoryApp::new();
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+
+        // signed by another account
+        let msgs = create.to_stargate_msgs::<TokenFactoryMsg>("other");
+        let err = app
+            .execute(Addr::unchecked("contract"), msgs[0].clone())
+            .unwrap_err();
+        assert!(err.to_string().contains("signed by other"), "{}", err);
+
+        // another package than the one of the app
+        let msgs = create.to_stargate_msgs_with_package::<TokenFactoryMsg>(
+            "contract",
+            "cosmwasm.tokenfactory.v1beta1",
+        );
+        let err = app
+            .execute(Addr::unchecked("contract"), msgs[0].clone())
+            .unwrap_err();
+        assert!(err.to_string().contains("Unsupported"), "{}", err);
+
+        let unknown = CosmosMsg::Stargate {
+            type_url: "/osmosis.tokenfactory.v1beta1.MsgUnknown".to_string(),
+            value: Binary::default(),
+        };
+        let err = app
+            .execute(Addr::unchecked("contract"), unknown)
+            .unwrap_err();
+        assert!(err.to_string().contains("Unsupported"), "{}", err);
+
+        let truncated = CosmosMsg::Stargate {
+            type_url: "/osmosis.tokenfactory.v1beta1.MsgCreateDenom".to_string(),
+            value: Binary::from(b"\x0a\x09contract"),
+        };
+        let err = app
+            .execute(Addr::unchecked("contract"), truncated)
+            .unwrap_err();
+        assert!(err.to_string().contains("too short"), "{}", err);
+    }
+
+    #[test]
+    fn custom_package() {
+        let package = "cosmwasm.tokenfactory.v1beta1";
+        let mut app = TokenFactoryAppBuilder::new()
+            .with_stargate_package(package)
+            .build();
+        let create = TokenFactoryMsg::Token(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        });
+        let msgs = create.to_stargate_msgs_with_package::<TokenFactoryMsg>("contract", package);
+        app.execute(Addr::unchecked("contract"), msgs[0].clone())
+            .unwrap();
+
+        let path = query_path(package, QUERY_DENOMS_FROM_CREATOR);
+        let data = string_request("contract");
+        let res: StargateDenomsFromCreatorResponse = app
+            .wrap()
+            .query(&QueryRequest::Stargate { path, data })
+            .unwrap();
+        assert_eq!(res.denoms, vec!["factory/contract/fundz".to_string()]);
+    }
+
+    #[test]
+    fn answers_stargate_queries() {
+        let creator = Addr::unchecked("creator");
+        let mut builder = TokenFactoryAppBuilder::new().with_params(Params {
+            denom_creation_fee: coins(100, "uosmo"),
+            denom_creation_gas_consume: 2_000_000,
+        });
+        // more than a page of the custom query
+        for i in 0..=MAX_DENOMS_LIMIT {
+            let admin = Addr::unchecked(format!("admin{}", i));
+            builder = builder.with_denom(creator.clone(), format!("d{:03}", i), admin, None);
+        }
+        let app = builder.build();
+
+        // Uint64 only deserializes from a string, like protobuf JSON encodes it
+        let res: StargateParamsResponse = query(&app, QUERY_PARAMS, Binary::default());
+        assert_eq!(
+            res.params,
+            StargateParams {
+                denom_creation_fee: coins(100, "uosmo"),
+                denom_creation_gas_consume: Uint64::new(2_000_000),
+            }
+        );
+
+        let res: StargateDenomAuthorityMetadataResponse = query(
+            &app,
+            QUERY_DENOM_AUTHORITY_METADATA,
+            string_request("factory/creator/d007"),
+        );
+        assert_eq!(res.authority_metadata.admin, "admin7");
+
+        let res: StargateDenomsFromCreatorResponse = query(
+            &app,
+            QUERY_DENOMS_FROM_CREATOR,
+            string_request(creator.as_str()),
+        );
+        assert_eq!(res.denoms.len(), MAX_DENOMS_LIMIT as usize + 1);
+        assert_eq!(res.denoms[100], "factory/creator/d100");
+
+        let err = app
+            .wrap()
+            .query::<Empty>(&QueryRequest::Stargate {
+                path: query_path(OSMOSIS_TOKENFACTORY_PACKAGE, "BeforeSendHookAddress"),
+                data: string_request("factory/creator/d007"),
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("Unsupported"), "{}", err);
+    }
+}
@@ -4,8 +4,9 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use token_bindings::{
-    AdminResponse, DenomsByCreatorResponse, FullDenomResponse, MetadataResponse, ParamsResponse,
-    TokenFactoryMsg, TokenFactoryQuery,
+    AdminResponse, AllDenomsResponse, BeforeSendHookAddressResponse, BeforeSendHookSudoMsg,
+    DenomsByCreatorResponse, FullDenomResponse, MetadataResponse, ParamsResponse, TokenFactoryMsg,
+    TokenFactoryQuery, TotalSupplyResponse,
 };
 
 fn main() {
@@ -21,4 +22,8 @@ fn main() {
     export_schema(&schema_for!(FullDenomResponse), &out_dir);
     export_schema(&schema_for!(MetadataResponse), &out_dir);
     export_schema(&schema_for!(ParamsResponse), &out_dir);
+    export_schema(&schema_for!(AllDenomsResponse), &out_dir);
+    export_schema(&schema_for!(TotalSupplyResponse), &out_dir);
+    export_schema(&schema_for!(BeforeSendHookAddressResponse), &out_dir);
+    export_schema(&schema_for!(BeforeSendHookSudoMsg), &out_dir);
 }
@@ -0,0 +1,396 @@
+use std::fmt;
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, StdError};
+use schemars::JsonSchema;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// Prefix of every denom created by the token factory module
+pub const FACTORY_DENOM_PREFIX: &str = "factory";
+/// Longest denom accepted by the bank module (sdk.ValidateDenom)
+pub const MAX_DENOM_LENGTH: usize = 128;
+/// Longest subdenom accepted by the token factory module
+pub const MAX_SUBDENOM_LENGTH: usize = 44;
+/// Longest creator address accepted by the token factory module
+pub const MAX_CREATOR_LENGTH: usize = 75;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DenomError {
+    #[error("denom must have at least 3 parts separated by /, had {parts}")]
+    MissingParts { parts: usize },
+
+    #[error("prefix must be 'factory', was {prefix}")]
+    InvalidPrefix { prefix: String },
+
+    #[error("invalid creator address '{creator}'")]
+    InvalidCreator { creator: String },
+
+    #[error("subdenom '{subdenom}' is longer than {MAX_SUBDENOM_LENGTH} characters")]
+    SubdenomTooLong { subdenom: String },
+
+    #[error("subdenom '{subdenom}' may only contain characters in [a-zA-Z0-9/:._-]")]
+    InvalidSubdenomCharacter { subdenom: String },
+
+    #[error("invalid denom '{denom}'")]
+    InvalidDenom { denom: String },
+}
+
+impl From<DenomError> for StdError {
+    fn from(err: DenomError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
+
+/// Checks a denom against the rules of the bank module (sdk.ValidateDenom), i.e. the regex
+/// [a-zA-Z][a-zA-Z0-9/:._-]{2,127}
+pub fn validate_native_denom(denom: &str) -> Result<(), DenomError> {
+    let mut chars = denom.chars();
+    let valid = (3..=MAX_DENOM_LENGTH).contains(&denom.len())
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(is_denom_char);
+    if !valid {
+        return Err(DenomError::InvalidDenom {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks a subdenom against the rules of the token factory module: at most 44 characters,
+/// which the bank module must accept in a denom. It may be empty or contain '/'.
+pub fn validate_subdenom(subdenom: &str) -> Result<(), DenomError> {
+    if subdenom.len() > MAX_SUBDENOM_LENGTH {
+        return Err(DenomError::SubdenomTooLong {
+            subdenom: subdenom.to_string(),
+        });
+    }
+    if !subdenom.chars().all(is_denom_char) {
+        return Err(DenomError::InvalidSubdenomCharacter {
+            subdenom: subdenom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Characters allowed after the first one of a denom
+fn is_denom_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "/:._-".contains(c)
+}
+
+/// A denom created by the token factory module, of the form
+/// factory/{creator}/{subdenom}
+///
+/// It can only be constructed through `FactoryDenom::new` or by parsing,
+/// so every instance satisfies the rules the chain enforces on creation:
+/// a bech32-like creator of at most 75 alphanumeric characters,
+/// a subdenom accepted by `validate_subdenom` and a full denom
+/// accepted by `validate_native_denom`.
+/// It (de)serializes as the full denom string and converts into a `String`,
+/// so it can be passed wherever the messages and queries take a denom.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FactoryDenom {
+    creator: Addr,
+    subdenom: String,
+}
+
+impl FactoryDenom {
+    pub fn new(
+        creator: impl Into<String>,
+        subdenom: impl Into<String>,
+    ) -> Result<Self, DenomError> {
+        let creator: String = creator.into();
+        let subdenom: String = subdenom.into();
+
+        if creator.is_empty()
+            || creator.len() > MAX_CREATOR_LENGTH
+            || !creator.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(DenomError::InvalidCreator { creator });
+        }
+        validate_subdenom(&subdenom)?;
+
+        let denom = FactoryDenom {
+            creator: Addr::unchecked(creator),
+            subdenom,
+        };
+        // the limits above keep the denom within MAX_DENOM_LENGTH,
+        // but the other rules of the bank module apply as well
+        validate_native_denom(&denom.to_string())?;
+        Ok(denom)
+    }
+
+    /// The address that created this denom
+    pub fn creator(&self) -> &Addr {
+        &self.creator
+    }
+
+    /// The subdenom chosen by the creator. May be empty or contain '/'
+    pub fn subdenom(&self) -> &str {
+        &self.subdenom
+    }
+}
+
+impl FromStr for FactoryDenom {
+    type Err = DenomError;
+
+    fn from_str(denom: &str) -> Result<Self, Self::Err> {
+        // the subdenom may itself contain '/', so only split off the first two parts
+        let parts: Vec<&str> = denom.splitn(3, '/').collect();
+        if parts.len() != 3 {
+            return Err(DenomError::MissingParts { parts: parts.len() });
+        }
+        if parts[0] != FACTORY_DENOM_PREFIX {
+            return Err(DenomError::InvalidPrefix {
+                prefix: parts[0].to_string(),
+            });
+        }
+        FactoryDenom::new(parts[1], parts[2])
+    }
+}
+
+impl fmt::Display for FactoryDenom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}",
+            FACTORY_DENOM_PREFIX, self.creator, self.subdenom
+        )
+    }
+}
+
+impl From<FactoryDenom> for String {
+    fn from(denom: FactoryDenom) -> Self {
+        denom.to_string()
+    }
+}
+
+impl Serialize for FactoryDenom {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for FactoryDenom {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let denom = String::deserialize(deserializer)?;
+        denom.parse().map_err(de::Error::custom)
+    }
+}
+
+impl JsonSchema for FactoryDenom {
+    fn schema_name() -> String {
+        "FactoryDenom".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{from_json, to_json_vec};
+
+    #[test]
+    fn parse_and_display() {
+        let denom: FactoryDenom = "factory/cosmos1creator/fundz".parse().unwrap();
+        assert_eq!(denom.creator().as_str(), "cosmos1creator");
+        assert_eq!(denom.subdenom(), "fundz");
+        assert_eq!(denom.to_string(), "factory/cosmos1creator/fundz");
+
+        // subdenoms may be empty or nested
+        let denom: FactoryDenom = "factory/cosmos1creator/".parse().unwrap();
+        assert_eq!(denom.subdenom(), "");
+        let denom: FactoryDenom = "factory/cosmos1creator/atom/derived.v2".parse().unwrap();
+        assert_eq!(denom.subdenom(), "atom/derived.v2");
+        assert_eq!(denom.to_string(), "factory/cosmos1creator/atom/derived.v2");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cases = [
+            ("uosmo", DenomError::MissingParts { parts: 1 }),
+            (
+                "factory/cosmos1creator",
+                DenomError::MissingParts { parts: 2 },
+            ),
+            (
+                "FACTORY/cosmos1creator/fundz",
+                DenomError::InvalidPrefix {
+                    prefix: "FACTORY".to_string(),
+                },
+            ),
+            (
+                "ibc/cosmos1creator/fundz",
+                DenomError::InvalidPrefix {
+                    prefix: "ibc".to_string(),
+                },
+            ),
+            (
+                "factory//fundz",
+                DenomError::InvalidCreator {
+                    creator: "".to_string(),
+                },
+            ),
+            (
+                "factory/cosmos1-creator/fundz",
+                DenomError::InvalidCreator {
+                    creator: "cosmos1-creator".to_string(),
+                },
+            ),
+            (
+                "factory/cosmos1creator/fun dz",
+                DenomError::InvalidSubdenomCharacter {
+                    subdenom: "fun dz".to_string(),
+                },
+            ),
+            (
+                "factory/cosmos1creator/fündz",
+                DenomError::InvalidSubdenomCharacter {
+                    subdenom: "fündz".to_string(),
+                },
+            ),
+        ];
+        for (denom, expected) in cases {
+            assert_eq!(
+                denom.parse::<FactoryDenom>().unwrap_err(),
+                expected,
+                "{}",
+                denom
+            );
+        }
+
+        let long_subdenom = "s".repeat(MAX_SUBDENOM_LENGTH + 1);
+        let err = FactoryDenom::new("cosmos1creator", long_subdenom.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            DenomError::SubdenomTooLong {
+                subdenom: long_subdenom
+            }
+        );
+
+        let long_creator = "c".repeat(MAX_CREATOR_LENGTH + 1);
+        let err = FactoryDenom::new(long_creator.clone(), "fundz").unwrap_err();
+        assert_eq!(
+            err,
+            DenomError::InvalidCreator {
+                creator: long_creator
+            }
+        );
+
+        // the longest valid denom is exactly at the bank limit
+        let denom = FactoryDenom::new(
+            "c".repeat(MAX_CREATOR_LENGTH),
+            "s".repeat(MAX_SUBDENOM_LENGTH),
+        )
+        .unwrap();
+        assert_eq!(denom.to_string().len(), MAX_DENOM_LENGTH);
+    }
+
+    #[test]
+    fn factory_denom_rules() {
+        let max_creator = "c".repeat(MAX_CREATOR_LENGTH);
+        let max_subdenom = "s".repeat(MAX_SUBDENOM_LENGTH);
+        let long_subdenom = "s".repeat(MAX_SUBDENOM_LENGTH + 1);
+        let invalid_creator = |creator: &str| {
+            Err(DenomError::InvalidCreator {
+                creator: creator.to_string(),
+            })
+        };
+        let invalid_subdenom = |subdenom: &str| {
+            Err(DenomError::InvalidSubdenomCharacter {
+                subdenom: subdenom.to_string(),
+            })
+        };
+        let cases: [(&str, &str, Result<(), DenomError>); 22] = [
+            // every character the bank module allows in a denom
+            ("osmo1creator", "fundz", Ok(())),
+            ("osmo1creator", "", Ok(())),
+            ("osmo1creator", "FUNDZ", Ok(())),
+            ("osmo1creator", "0fundz", Ok(())),
+            ("osmo1creator", "fun.dz", Ok(())),
+            ("osmo1creator", "fun/dz", Ok(())),
+            ("osmo1creator", "fun:dz", Ok(())),
+            ("osmo1creator", "fun_dz", Ok(())),
+            ("osmo1creator", "fun-dz", Ok(())),
+            ("osmo1creator", "/", Ok(())),
+            ("OSMO1CREATOR", "fundz", Ok(())),
+            (&max_creator, &max_subdenom, Ok(())),
+            // and nothing else
+            ("osmo1creator", "fun dz", invalid_subdenom("fun dz")),
+            ("osmo1creator", "fündz", invalid_subdenom("fündz")),
+            ("osmo1creator", "fun+dz", invalid_subdenom("fun+dz")),
+            ("osmo1creator", "fun\ndz", invalid_subdenom("fun\ndz")),
+            (
+                "osmo1creator",
+                &long_subdenom,
+                Err(DenomError::SubdenomTooLong {
+                    subdenom: long_subdenom.clone(),
+                }),
+            ),
+            ("", "fundz", invalid_creator("")),
+            ("osmo1/creator", "fundz", invalid_creator("osmo1/creator")),
+            ("osmo1_creator", "fundz", invalid_creator("osmo1_creator")),
+            ("osmo1créator", "fundz", invalid_creator("osmo1créator")),
+            (
+                &format!("{}c", max_creator),
+                "fundz",
+                invalid_creator(&format!("{}c", max_creator)),
+            ),
+        ];
+        for (creator, subdenom, expected) in cases {
+            match FactoryDenom::new(creator, subdenom) {
+                Ok(denom) => {
+                    assert_eq!(expected, Ok(()), "{}/{}", creator, subdenom);
+                    // every factory denom is a valid bank denom and parses back
+                    validate_native_denom(&denom.to_string()).unwrap();
+                    assert_eq!(denom.to_string().parse(), Ok(denom));
+                }
+                Err(err) => assert_eq!(Err(err), expected, "{}/{}", creator, subdenom),
+            }
+        }
+    }
+
+    #[test]
+    fn native_denoms() {
+        for denom in [
+            "uosmo",
+            "ibc/27394FB092D2ECCD",
+            "factory/osmo1abc/sub.v2",
+            "a:b-c_d",
+        ] {
+            validate_native_denom(denom).unwrap();
+        }
+        let too_long = format!("u{}", "a".repeat(MAX_DENOM_LENGTH));
+        for denom in [
+            "",
+            "ab",
+            "1uosmo",
+            "/uosmo",
+            "uo smo",
+            "uösmo",
+            too_long.as_str(),
+        ] {
+            assert_eq!(
+                validate_native_denom(denom).unwrap_err(),
+                DenomError::InvalidDenom {
+                    denom: denom.to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn serde_as_string() {
+        let denom = FactoryDenom::new("cosmos1creator", "fundz").unwrap();
+        let json = to_json_vec(&denom).unwrap();
+        assert_eq!(json, br#""factory/cosmos1creator/fundz""#);
+        let parsed: FactoryDenom = from_json(&json).unwrap();
+        assert_eq!(parsed, denom);
+
+        let err = from_json::<FactoryDenom>(br#""FACTORY/cosmos1creator/fundz""#).unwrap_err();
+        assert!(err.to_string().contains("prefix must be 'factory'"));
+    }
+}
@@ -1,12 +1,27 @@
+mod denom;
 mod msg;
+// only the CreateDenomResponse encoding is used without the stargate feature
+#[cfg_attr(not(feature = "stargate"), allow(dead_code))]
+mod protobuf;
 mod querier;
 mod query;
+#[cfg(feature = "stargate")]
+pub mod stargate;
+mod sudo;
 mod types;
+mod units;
 
+pub use denom::{
+    validate_native_denom, validate_subdenom, DenomError, FactoryDenom, FACTORY_DENOM_PREFIX,
+    MAX_CREATOR_LENGTH, MAX_DENOM_LENGTH, MAX_SUBDENOM_LENGTH,
+};
 pub use msg::{CreateDenomResponse, TokenFactoryMsg, TokenFactoryMsgOptions};
-pub use querier::TokenQuerier;
+pub use querier::{DenomsIter, TokenQuerier};
 pub use query::{
-    AdminResponse, DenomsByCreatorResponse, FullDenomResponse, MetadataResponse, ParamsResponse,
-    TokenFactoryQuery, TokenFactoryQueryEnum,
+    AdminResponse, AllDenomsResponse, BeforeSendHookAddressResponse, DenomsByCreatorResponse,
+    FullDenomResponse, MetadataResponse, ParamsResponse, TokenFactoryQuery, TokenFactoryQueryEnum,
+    TotalSupplyResponse,
 };
-pub use types::{DenomUnit, Metadata, Params};
+pub use sudo::BeforeSendHookSudoMsg;
+pub use types::{DenomUnit, Metadata, MetadataBuilder, MetadataError, Params};
+pub use units::ConversionError;
@@ -1,3 +1,4 @@
+use crate::protobuf::encode_protobuf_string;
 use crate::types::Metadata;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Binary, CosmosMsg, CustomMsg, StdResult, Uint128};
@@ -12,7 +13,7 @@ pub enum TokenFactoryMsg {
 pub enum TokenFactoryMsgOptions {
     /// CreateDenom creates a new factory denom, of denomination:
     /// factory/{creating contract bech32 address}/{Subdenom}
-    /// Subdenom can be of length at most 44 characters, in [0-9a-zA-Z./]
+    /// Subdenom can be of length at most 44 characters, in [a-zA-Z0-9/:._-]
     /// Empty subdenoms are valid.
     /// The (creating contract address, subdenom) pair must be unique.
     /// The created denom's admin is the creating contract address,
@@ -34,6 +35,7 @@ pub enum TokenFactoryMsgOptions {
     },
     /// Contracts can mint native tokens for an existing factory denom
     /// that they are the admin of.
+    /// If mint_to_address is empty, the tokens are minted to the sender.
     MintTokens {
         denom: String,
         amount: Uint128,
@@ -41,6 +43,7 @@ pub enum TokenFactoryMsgOptions {
     },
     /// Contracts can burn native tokens for an existing factory denom
     /// tshat they are the admin of.
+    /// If burn_from_address is empty, the tokens are burned from the sender.
     BurnTokens {
         denom: String,
         amount: Uint128,
@@ -58,43 +61,90 @@ pub enum TokenFactoryMsgOptions {
         denom: String,
         metadata: Metadata,
     },
+    /// SetBeforeSendHook registers a contract whose sudo entry point is called
+    /// with a `BeforeSendHookSudoMsg` on every bank transfer of the denom.
+    /// Can only be called by the current denom admin.
+    /// If contract_addr is empty, the hook is removed.
+    SetBeforeSendHook {
+        denom: String,
+        contract_addr: String,
+    },
 }
 
 impl TokenFactoryMsg {
-    pub fn mint_contract_tokens(denom: String, amount: Uint128, mint_to_address: String) -> Self {
+    pub fn mint_contract_tokens(
+        denom: impl Into<String>,
+        amount: Uint128,
+        mint_to_address: String,
+    ) -> Self {
         TokenFactoryMsg::Token(TokenFactoryMsgOptions::MintTokens {
-            denom,
+            denom: denom.into(),
             amount,
             mint_to_address,
         })
     }
 
-    pub fn burn_contract_tokens(denom: String, amount: Uint128, burn_from_address: String) -> Self {
+    pub fn burn_contract_tokens(
+        denom: impl Into<String>,
+        amount: Uint128,
+        burn_from_address: String,
+    ) -> Self {
         TokenFactoryMsg::Token(TokenFactoryMsgOptions::BurnTokens {
-            denom,
+            denom: denom.into(),
             amount,
             burn_from_address,
         })
     }
 
     pub fn force_transfer_tokens(
-        denom: String,
+        denom: impl Into<String>,
         amount: Uint128,
         from_address: String,
         to_address: String,
     ) -> Self {
         TokenFactoryMsg::Token(TokenFactoryMsgOptions::ForceTransfer {
-            denom,
+            denom: denom.into(),
             amount,
             from_address,
             to_address,
         })
     }
+
+    pub fn change_admin(denom: impl Into<String>, new_admin_address: String) -> Self {
+        TokenFactoryMsg::Token(TokenFactoryMsgOptions::ChangeAdmin {
+            denom: denom.into(),
+            new_admin_address,
+        })
+    }
+
+    /// Leaves the denom without an admin, so nobody can mint, burn or change it anymore
+    pub fn renounce_admin(denom: impl Into<String>) -> Self {
+        Self::change_admin(denom, String::new())
+    }
+
+    pub fn set_metadata(denom: impl Into<String>, metadata: Metadata) -> Self {
+        TokenFactoryMsg::Token(TokenFactoryMsgOptions::SetMetadata {
+            denom: denom.into(),
+            metadata,
+        })
+    }
+
+    pub fn set_before_send_hook(denom: impl Into<String>, contract_addr: String) -> Self {
+        TokenFactoryMsg::Token(TokenFactoryMsgOptions::SetBeforeSendHook {
+            denom: denom.into(),
+            contract_addr,
+        })
+    }
 }
 
-impl From<TokenFactoryMsg> for CosmosMsg<TokenFactoryMsg> {
-    fn from(msg: TokenFactoryMsg) -> CosmosMsg<TokenFactoryMsg> {
-        CosmosMsg::Custom(msg)
+/// Works for `CosmosMsg<TokenFactoryMsg>` as well as for any custom message type
+/// wrapping a `TokenFactoryMsg`, so the bindings compose with other chain modules.
+impl<C> From<TokenFactoryMsg> for CosmosMsg<C>
+where
+    C: From<TokenFactoryMsg>,
+{
+    fn from(msg: TokenFactoryMsg) -> CosmosMsg<C> {
+        CosmosMsg::Custom(msg.into())
     }
 }
 
@@ -116,9 +166,12 @@ impl CreateDenomResponse {
         Ok(CreateDenomResponse { new_token_denom })
     }
 
+    /// Encodes this response the same way the chain does, so it can be used
+    /// as the data field of a mocked CreateDenom reply
     pub fn encode(&self) -> StdResult<Binary> {
-        // TODO
-        Ok(b"".into())
+        let mut data = vec![];
+        encode_protobuf_string(&mut data, 1, &self.new_token_denom);
+        Ok(data.into())
     }
 }
 
@@ -216,3 +269,86 @@ mod copied_from_cw_utils {
         Ok(len as usize) // Gently fall back to the arch's max addressable size
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::Response;
+
+    /// A custom message type of a chain with more modules than the token factory
+    #[cw_serde]
+    enum ChainMsg {
+        Token(TokenFactoryMsg),
+        Oracle { pair: String },
+    }
+
+    impl CustomMsg for ChainMsg {}
+
+    impl From<TokenFactoryMsg> for ChainMsg {
+        fn from(msg: TokenFactoryMsg) -> Self {
+            ChainMsg::Token(msg)
+        }
+    }
+
+    #[test]
+    fn composite_custom_msg() {
+        let msg = TokenFactoryMsg::mint_contract_tokens(
+            "factory/creator/fundz",
+            Uint128::new(100),
+            "rcpt".to_string(),
+        );
+        let res: Response<ChainMsg> = Response::new().add_message(msg.clone());
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Custom(ChainMsg::Token(msg.clone()))
+        );
+
+        // the plain token factory message type still works as before
+        let res: Response<TokenFactoryMsg> = Response::new().add_message(msg.clone());
+        assert_eq!(res.messages[0].msg, CosmosMsg::Custom(msg));
+    }
+
+    #[test]
+    fn create_denom_response_round_trip() {
+        let denoms = [
+            "factory/cosmos1creator/sub".to_string(),
+            // long enough that the length prefix needs a two byte varint
+            format!("factory/{}/{}", "c".repeat(75), "s".repeat(44)),
+        ];
+        for new_token_denom in denoms {
+            let response = CreateDenomResponse {
+                new_token_denom: new_token_denom.clone(),
+            };
+            let data = response.encode().unwrap();
+            let parsed = CreateDenomResponse::from_reply_data(data).unwrap();
+            assert_eq!(parsed.new_token_denom, new_token_denom);
+        }
+    }
+
+    #[test]
+    fn create_denom_response_encoding() {
+        let response = CreateDenomResponse {
+            new_token_denom: "abc".to_string(),
+        };
+        let data = response.encode().unwrap();
+        assert_eq!(data.as_slice(), b"\x0a\x03abc");
+
+        let long = "x".repeat(200);
+        let response = CreateDenomResponse {
+            new_token_denom: long.clone(),
+        };
+        let data = response.encode().unwrap();
+        assert_eq!(&data.as_slice()[..3], &[0x0a, 0xc8, 0x01]);
+        assert_eq!(&data.as_slice()[3..], long.as_bytes());
+
+        // empty denom is the protobuf default and encodes to nothing
+        let response = CreateDenomResponse {
+            new_token_denom: String::new(),
+        };
+        let data = response.encode().unwrap();
+        assert!(data.is_empty());
+        let parsed = CreateDenomResponse::from_reply_data(data).unwrap();
+        assert_eq!(parsed.new_token_denom, "");
+    }
+}
@@ -0,0 +1,61 @@
+//! Minimal protobuf encoding, without pulling in prost.
+//! Fields with the proto3 default value are omitted, like protobuf encoders do.
+
+// Protobuf wire types (https://developers.google.com/protocol-buffers/docs/encoding)
+const WIRE_TYPE_VARINT: u8 = 0;
+const WIRE_TYPE_LENGTH_DELIMITED: u8 = 2;
+
+/// Appends a string field to data.
+/// Empty strings are the proto3 default value, so they are omitted.
+pub fn encode_protobuf_string(data: &mut Vec<u8>, field_number: u8, value: &str) {
+    encode_protobuf_bytes(data, field_number, value.as_bytes());
+}
+
+/// Appends a repeated string field to data, one entry per value.
+/// Unlike singular fields, empty entries are kept.
+pub fn encode_protobuf_repeated_string(data: &mut Vec<u8>, field_number: u8, values: &[String]) {
+    for value in values {
+        encode_protobuf_message(data, field_number, value.as_bytes());
+    }
+}
+
+/// Appends an embedded message field to data, given the encoded message.
+/// Unlike scalar fields, set messages are kept even if empty.
+pub fn encode_protobuf_message(data: &mut Vec<u8>, field_number: u8, message: &[u8]) {
+    encode_protobuf_key(data, field_number, WIRE_TYPE_LENGTH_DELIMITED);
+    encode_protobuf_varint(data, message.len() as u64);
+    data.extend_from_slice(message);
+}
+
+/// Appends an unsigned integer field (uint32 or uint64) to data.
+/// Zero is the proto3 default value, so it is omitted.
+pub fn encode_protobuf_uint(data: &mut Vec<u8>, field_number: u8, value: u64) {
+    if value == 0 {
+        return;
+    }
+    encode_protobuf_key(data, field_number, WIRE_TYPE_VARINT);
+    encode_protobuf_varint(data, value);
+}
+
+/// Helper function to append length-prefixed protobuf fields.
+fn encode_protobuf_bytes(data: &mut Vec<u8>, field_number: u8, value: &[u8]) {
+    if value.is_empty() {
+        return;
+    }
+    encode_protobuf_key(data, field_number, WIRE_TYPE_LENGTH_DELIMITED);
+    encode_protobuf_varint(data, value.len() as u64);
+    data.extend_from_slice(value);
+}
+
+fn encode_protobuf_key(data: &mut Vec<u8>, field_number: u8, wire_type: u8) {
+    encode_protobuf_varint(data, ((field_number as u64) << 3) | wire_type as u64);
+}
+
+/// Base128 varint encoding.
+fn encode_protobuf_varint(data: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        data.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    data.push(value as u8);
+}
@@ -1,45 +1,302 @@
-use cosmwasm_std::{QuerierWrapper, StdResult};
+use cosmwasm_std::{Addr, CustomQuery, QuerierWrapper, QueryRequest, StdResult};
+use serde::de::DeserializeOwned;
 
 use crate::{
     query::{FullDenomResponse, TokenFactoryQuery, TokenFactoryQueryEnum},
-    DenomsByCreatorResponse, MetadataResponse, ParamsResponse,
+    AdminResponse, AllDenomsResponse, BeforeSendHookAddressResponse, DenomsByCreatorResponse,
+    FactoryDenom, MetadataResponse, ParamsResponse, TotalSupplyResponse,
 };
 
-/// This is a helper wrapper to easily use our custom queries
-pub struct TokenQuerier<'a> {
-    querier: &'a QuerierWrapper<'a, TokenFactoryQuery>,
+/// This is a helper wrapper to easily use our custom queries.
+///
+/// It works with any custom query type that can wrap a `TokenFactoryQuery`,
+/// so contracts combining several chain modules in one query enum can use it too.
+pub struct TokenQuerier<'a, C: CustomQuery = TokenFactoryQuery> {
+    querier: &'a QuerierWrapper<'a, C>,
 }
 
-impl<'a> TokenQuerier<'a> {
-    pub fn new(querier: &'a QuerierWrapper<TokenFactoryQuery>) -> Self {
+impl<'a, C> TokenQuerier<'a, C>
+where
+    C: CustomQuery + From<TokenFactoryQuery>,
+{
+    pub fn new(querier: &'a QuerierWrapper<C>) -> Self {
         TokenQuerier { querier }
     }
 
+    fn query<T: DeserializeOwned>(&self, query: TokenFactoryQueryEnum) -> StdResult<T> {
+        let request = QueryRequest::Custom(C::from(TokenFactoryQuery::Token(query)));
+        self.querier.query(&request)
+    }
+
     pub fn full_denom(
         &self,
         creator_addr: String,
         subdenom: String,
     ) -> StdResult<FullDenomResponse> {
-        let full_denom_query = TokenFactoryQuery::Token(TokenFactoryQueryEnum::FullDenom {
+        self.query(TokenFactoryQueryEnum::FullDenom {
             creator_addr,
             subdenom,
-        });
-        self.querier.query(&full_denom_query.into())
+        })
     }
 
+    /// Like `full_denom`, but parses the response into a `FactoryDenom`
+    pub fn factory_denom(&self, creator_addr: String, subdenom: String) -> StdResult<FactoryDenom> {
+        let FullDenomResponse { denom } = self.full_denom(creator_addr, subdenom)?;
+        Ok(denom.parse()?)
+    }
+
+    /// Returns all denoms created by `creator`, querying as many pages as needed.
+    /// Use `denoms_by_creator_page` to query a single page.
     pub fn denom_by_creator(&self, creator: String) -> StdResult<DenomsByCreatorResponse> {
-        let denom_by_creator_query =
-            TokenFactoryQuery::Token(TokenFactoryQueryEnum::DenomsByCreator { creator });
-        self.querier.query(&denom_by_creator_query.into())
+        let denoms = self
+            .denoms_by_creator_iter(creator, None)
+            .collect::<StdResult<_>>()?;
+        Ok(DenomsByCreatorResponse {
+            denoms,
+            next_start_after: None,
+        })
+    }
+
+    pub fn denoms_by_creator_page(
+        &self,
+        creator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<DenomsByCreatorResponse> {
+        self.query(TokenFactoryQueryEnum::DenomsByCreator {
+            creator,
+            start_after,
+            limit,
+        })
+    }
+
+    /// Iterates over all denoms created by `creator`, querying one page of
+    /// (at most) `page_size` denoms at a time as needed.
+    pub fn denoms_by_creator_iter(
+        &self,
+        creator: String,
+        page_size: Option<u32>,
+    ) -> DenomsIter<'_, 'a, C> {
+        DenomsIter::new(self, Some(creator), page_size)
+    }
+
+    pub fn all_denoms_page(
+        &self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllDenomsResponse> {
+        self.query(TokenFactoryQueryEnum::AllDenoms { start_after, limit })
+    }
+
+    /// Iterates over all factory denoms, querying one page of
+    /// (at most) `page_size` denoms at a time as needed.
+    pub fn all_denoms_iter(&self, page_size: Option<u32>) -> DenomsIter<'_, 'a, C> {
+        DenomsIter::new(self, None, page_size)
+    }
+
+    pub fn total_supply(&self, denom: impl Into<String>) -> StdResult<TotalSupplyResponse> {
+        self.query(TokenFactoryQueryEnum::TotalSupply {
+            denom: denom.into(),
+        })
+    }
+
+    pub fn metadata(&self, denom: impl Into<String>) -> StdResult<MetadataResponse> {
+        self.query(TokenFactoryQueryEnum::Metadata {
+            denom: denom.into(),
+        })
     }
 
-    pub fn metadata(&self, denom: String) -> StdResult<MetadataResponse> {
-        let metadata_query = TokenFactoryQuery::Token(TokenFactoryQueryEnum::Metadata { denom });
-        self.querier.query(&metadata_query.into())
+    pub fn admin(&self, denom: impl Into<String>) -> StdResult<AdminResponse> {
+        self.query(TokenFactoryQueryEnum::Admin {
+            denom: denom.into(),
+        })
+    }
+
+    /// Returns the admin of the denom, or None if there is none.
+    /// That is the case for denoms not created by the token factory
+    /// (which are not queried at all) and for denoms whose admin was renounced.
+    pub fn try_admin(&self, denom: impl Into<String>) -> StdResult<Option<Addr>> {
+        let denom: String = denom.into();
+        if denom.parse::<FactoryDenom>().is_err() {
+            return Ok(None);
+        }
+        let AdminResponse { admin } = self.admin(denom)?;
+        if admin.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Addr::unchecked(admin)))
+        }
+    }
+
+    pub fn before_send_hook_address(
+        &self,
+        denom: impl Into<String>,
+    ) -> StdResult<BeforeSendHookAddressResponse> {
+        self.query(TokenFactoryQueryEnum::BeforeSendHookAddress {
+            denom: denom.into(),
+        })
     }
 
     pub fn params(&self) -> StdResult<ParamsResponse> {
-        let params_query = TokenFactoryQuery::Token(TokenFactoryQueryEnum::Params {});
-        self.querier.query(&params_query.into())
+        self.query(TokenFactoryQueryEnum::Params {})
+    }
+}
+
+/// Iterator over paginated denom lists, see `TokenQuerier::denoms_by_creator_iter`
+/// and `TokenQuerier::all_denoms_iter`
+pub struct DenomsIter<'q, 'a, C: CustomQuery> {
+    querier: &'q TokenQuerier<'a, C>,
+    /// Only list denoms of this creator, or all of them if None
+    creator: Option<String>,
+    page_size: Option<u32>,
+    page: std::vec::IntoIter<String>,
+    start_after: Option<String>,
+    done: bool,
+}
+
+impl<'q, 'a, C> DenomsIter<'q, 'a, C>
+where
+    C: CustomQuery + From<TokenFactoryQuery>,
+{
+    fn new(
+        querier: &'q TokenQuerier<'a, C>,
+        creator: Option<String>,
+        page_size: Option<u32>,
+    ) -> Self {
+        DenomsIter {
+            querier,
+            creator,
+            page_size,
+            page: vec![].into_iter(),
+            start_after: None,
+            done: false,
+        }
+    }
+
+    /// Returns the denoms of the next page and where the page after starts
+    fn next_page(&mut self) -> StdResult<(Vec<String>, Option<String>)> {
+        let start_after = self.start_after.take();
+        match &self.creator {
+            Some(creator) => {
+                let DenomsByCreatorResponse {
+                    denoms,
+                    next_start_after,
+                } = self.querier.denoms_by_creator_page(
+                    creator.clone(),
+                    start_after,
+                    self.page_size,
+                )?;
+                Ok((denoms, next_start_after))
+            }
+            None => {
+                let AllDenomsResponse {
+                    denoms,
+                    next_start_after,
+                } = self.querier.all_denoms_page(start_after, self.page_size)?;
+                Ok((denoms, next_start_after))
+            }
+        }
+    }
+}
+
+impl<'q, 'a, C> Iterator for DenomsIter<'q, 'a, C>
+where
+    C: CustomQuery + From<TokenFactoryQuery>,
+{
+    type Item = StdResult<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // a page may be empty, e.g. if its denoms were filtered out, without being the last one
+        loop {
+            if let Some(denom) = self.page.next() {
+                return Some(Ok(denom));
+            }
+            if self.done {
+                return None;
+            }
+            match self.next_page() {
+                Ok((denoms, next_start_after)) => {
+                    self.done = next_start_after.is_none();
+                    self.start_after = next_start_after;
+                    self.page = denoms.into_iter();
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::testing::MockQuerier;
+    use cosmwasm_std::{to_json_binary, ContractResult, SystemResult};
+
+    /// A custom query type of a chain with more modules than the token factory
+    #[cw_serde]
+    enum ChainQuery {
+        Token(TokenFactoryQuery),
+        Oracle { pair: String },
+    }
+
+    impl CustomQuery for ChainQuery {}
+
+    impl From<TokenFactoryQuery> for ChainQuery {
+        fn from(query: TokenFactoryQuery) -> Self {
+            ChainQuery::Token(query)
+        }
+    }
+
+    #[test]
+    fn composite_custom_query() {
+        let querier = MockQuerier::<ChainQuery>::new(&[]).with_custom_handler(|query| {
+            let res = match query {
+                ChainQuery::Token(TokenFactoryQuery::Token(TokenFactoryQueryEnum::Admin {
+                    denom,
+                })) => to_json_binary(&AdminResponse {
+                    admin: format!("admin of {}", denom),
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        });
+        let wrapper = QuerierWrapper::<ChainQuery>::new(&querier);
+
+        let AdminResponse { admin } = TokenQuerier::new(&wrapper).admin("uosmo").unwrap();
+        assert_eq!(admin, "admin of uosmo");
+    }
+
+    #[test]
+    fn iterates_past_empty_pages() {
+        let querier = MockQuerier::<TokenFactoryQuery>::new(&[]).with_custom_handler(|query| {
+            let TokenFactoryQuery::Token(TokenFactoryQueryEnum::AllDenoms { start_after, .. }) =
+                query
+            else {
+                panic!("unexpected query");
+            };
+            let (denoms, next_start_after) = match start_after.as_deref() {
+                None => (vec!["factory/c/a"], Some("factory/c/a")),
+                Some("factory/c/a") => (vec![], Some("factory/c/b")),
+                Some("factory/c/b") => (vec![], Some("factory/c/c")),
+                Some("factory/c/c") => (vec!["factory/c/d"], None),
+                _ => panic!("unexpected page"),
+            };
+            let res = to_json_binary(&AllDenomsResponse {
+                denoms: denoms.into_iter().map(String::from).collect(),
+                next_start_after: next_start_after.map(String::from),
+            });
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        });
+        let wrapper = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+
+        let denoms: Vec<String> = TokenQuerier::new(&wrapper)
+            .all_denoms_iter(Some(1))
+            .collect::<StdResult<_>>()
+            .unwrap();
+        assert_eq!(denoms, vec!["factory/c/a", "factory/c/d"]);
     }
 }
@@ -1,6 +1,6 @@
 use crate::types::{Metadata, Params};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::CustomQuery;
+use cosmwasm_std::{Coin, CustomQuery};
 
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -29,26 +29,51 @@ pub enum TokenFactoryQueryEnum {
     #[returns(MetadataResponse)]
     Metadata { denom: String },
     /// Returns info on admin of the denom, only if created/managed via token factory.
-    /// Errors if denom doesn't exist or was created by another module.
+    /// The admin is empty if the denom doesn't exist, was created by another module
+    /// or its admin was renounced.
     #[returns(AdminResponse)]
     Admin { denom: String },
     /// List all denoms that were created by the given creator.
     /// This does not imply all tokens currently managed by the creator.
     /// (Admin may have changed)
+    /// Results are ordered by denom and paginated: pass the `next_start_after`
+    /// of the previous response as `start_after` to get the next page.
     #[returns(DenomsByCreatorResponse)]
-    DenomsByCreator { creator: String },
+    DenomsByCreator {
+        creator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Returns configuration params for TokenFactory modules
     #[returns(ParamsResponse)]
     Params {},
+    /// List all denoms created via the token factory, by any creator.
+    /// Paginated like `DenomsByCreator`.
+    #[returns(AllDenomsResponse)]
+    AllDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the current total supply of the given denom,
+    /// which is zero for denoms that were never minted.
+    #[returns(TotalSupplyResponse)]
+    TotalSupply { denom: String },
+    /// Returns the contract registered via `SetBeforeSendHook` for this denom, if any.
+    #[returns(BeforeSendHookAddressResponse)]
+    BeforeSendHookAddress { denom: String },
 }
 
 #[cw_serde]
+#[allow(clippy::enum_variant_names)]
 pub enum TokenQueryResponse {
     FullDenomResponse(FullDenomResponse),
     MetadataResponse(MetadataResponse),
     AdminResponse(AdminResponse),
     DenomsByCreatorResponse(DenomsByCreatorResponse),
     ParamsResponse(ParamsResponse),
+    AllDenomsResponse(AllDenomsResponse),
+    TotalSupplyResponse(TotalSupplyResponse),
+    BeforeSendHookAddressResponse(BeforeSendHookAddressResponse),
 }
 
 #[cw_serde]
@@ -64,15 +89,40 @@ pub struct MetadataResponse {
 
 #[cw_serde]
 pub struct AdminResponse {
+    /// Empty if the denom has no admin
     pub admin: String,
 }
 
 #[cw_serde]
 pub struct DenomsByCreatorResponse {
     pub denoms: Vec<String>,
+    /// Set if there are more denoms after this page.
+    /// Chains without pagination return every denom at once and leave this empty.
+    #[serde(default)]
+    pub next_start_after: Option<String>,
 }
 
+/// `params.denom_creation_gas_consume` is 0 if the chain doesn't support it
 #[cw_serde]
 pub struct ParamsResponse {
     pub params: Params,
 }
+
+#[cw_serde]
+pub struct AllDenomsResponse {
+    pub denoms: Vec<String>,
+    /// Set if there are more denoms after this page
+    #[serde(default)]
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct TotalSupplyResponse {
+    pub amount: Coin,
+}
+
+#[cw_serde]
+pub struct BeforeSendHookAddressResponse {
+    /// `None` if no hook is registered for the denom
+    pub contract_addr: Option<String>,
+}
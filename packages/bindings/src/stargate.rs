@@ -0,0 +1,351 @@
+//! Encoding of token factory messages as `CosmosMsg::Stargate`, for chains that
+//! support the token factory module but not the custom message bindings.
+//!
+//! Enable it with the `stargate` feature. Messages are encoded by hand to keep
+//! prost out of the contract.
+//!
+//! ```
+//! use cosmwasm_std::{CosmosMsg, Empty, Uint128};
+//! use token_bindings::TokenFactoryMsg;
+//!
+//! let mint = TokenFactoryMsg::mint_contract_tokens(
+//!     "factory/osmo1contract/fundz",
+//!     Uint128::new(100),
+//!     "osmo1recipient".to_string(),
+//! );
+//! let msgs: Vec<CosmosMsg<Empty>> = mint.to_stargate_msgs("osmo1contract");
+//! ```
+
+use cosmwasm_std::{Binary, CosmosMsg, Uint128};
+
+use crate::protobuf::{
+    encode_protobuf_message, encode_protobuf_repeated_string, encode_protobuf_string,
+    encode_protobuf_uint,
+};
+use crate::{DenomUnit, Metadata, TokenFactoryMsg, TokenFactoryMsgOptions};
+
+/// Protobuf package of the token factory module of Osmosis,
+/// which chains forked from it may have renamed
+pub const OSMOSIS_TOKENFACTORY_PACKAGE: &str = "osmosis.tokenfactory.v1beta1";
+
+pub const MSG_CREATE_DENOM: &str = "MsgCreateDenom";
+pub const MSG_MINT: &str = "MsgMint";
+pub const MSG_BURN: &str = "MsgBurn";
+pub const MSG_CHANGE_ADMIN: &str = "MsgChangeAdmin";
+pub const MSG_SET_DENOM_METADATA: &str = "MsgSetDenomMetadata";
+pub const MSG_SET_BEFORE_SEND_HOOK: &str = "MsgSetBeforeSendHook";
+pub const MSG_FORCE_TRANSFER: &str = "MsgForceTransfer";
+
+/// Type URL of a message of the given protobuf package, e.g.
+/// /osmosis.tokenfactory.v1beta1.MsgCreateDenom
+pub fn type_url(package: &str, msg: &str) -> String {
+    format!("/{}.{}", package, msg)
+}
+
+impl TokenFactoryMsg {
+    /// See `TokenFactoryMsgOptions::to_stargate_msgs`
+    pub fn to_stargate_msgs<T>(&self, sender: &str) -> Vec<CosmosMsg<T>> {
+        let TokenFactoryMsg::Token(msg) = self;
+        msg.to_stargate_msgs(sender)
+    }
+
+    /// See `TokenFactoryMsgOptions::to_stargate_msgs_with_package`
+    pub fn to_stargate_msgs_with_package<T>(
+        &self,
+        sender: &str,
+        package: &str,
+    ) -> Vec<CosmosMsg<T>> {
+        let TokenFactoryMsg::Token(msg) = self;
+        msg.to_stargate_msgs_with_package(sender, package)
+    }
+}
+
+impl TokenFactoryMsgOptions {
+    /// Encodes this message as the equivalent Osmosis token factory messages,
+    /// sent by `sender` (the contract address).
+    ///
+    /// This is a single message, except for `CreateDenom` with metadata,
+    /// which becomes a `MsgCreateDenom` followed by a `MsgSetDenomMetadata`.
+    pub fn to_stargate_msgs<T>(&self, sender: &str) -> Vec<CosmosMsg<T>> {
+        self.to_stargate_msgs_with_package(sender, OSMOSIS_TOKENFACTORY_PACKAGE)
+    }
+
+    /// Like `to_stargate_msgs`, for chains that use another protobuf package
+    /// for the same messages
+    pub fn to_stargate_msgs_with_package<T>(
+        &self,
+        sender: &str,
+        package: &str,
+    ) -> Vec<CosmosMsg<T>> {
+        let stargate = |msg: &str, value: Vec<u8>| CosmosMsg::Stargate {
+            type_url: type_url(package, msg),
+            value: Binary::from(value),
+        };
+        match self {
+            TokenFactoryMsgOptions::CreateDenom { subdenom, metadata } => {
+                let mut value = vec![];
+                encode_protobuf_string(&mut value, 1, sender);
+                encode_protobuf_string(&mut value, 2, subdenom);
+                let mut msgs = vec![stargate(MSG_CREATE_DENOM, value)];
+                if let Some(metadata) = metadata {
+                    let denom = format!("factory/{}/{}", sender, subdenom);
+                    let value = encode_set_denom_metadata(sender, &denom, metadata);
+                    msgs.push(stargate(MSG_SET_DENOM_METADATA, value));
+                }
+                msgs
+            }
+            TokenFactoryMsgOptions::ChangeAdmin {
+                denom,
+                new_admin_address,
+            } => {
+                let mut value = vec![];
+                encode_protobuf_string(&mut value, 1, sender);
+                encode_protobuf_string(&mut value, 2, denom);
+                encode_protobuf_string(&mut value, 3, new_admin_address);
+                vec![stargate(MSG_CHANGE_ADMIN, value)]
+            }
+            TokenFactoryMsgOptions::MintTokens {
+                denom,
+                amount,
+                mint_to_address,
+            } => {
+                let mut value = vec![];
+                encode_protobuf_string(&mut value, 1, sender);
+                encode_protobuf_message(&mut value, 2, &encode_coin(denom, *amount));
+                encode_protobuf_string(&mut value, 3, mint_to_address);
+                vec![stargate(MSG_MINT, value)]
+            }
+            TokenFactoryMsgOptions::BurnTokens {
+                denom,
+                amount,
+                burn_from_address,
+            } => {
+                let mut value = vec![];
+                encode_protobuf_string(&mut value, 1, sender);
+                encode_protobuf_message(&mut value, 2, &encode_coin(denom, *amount));
+                encode_protobuf_string(&mut value, 3, burn_from_address);
+                vec![stargate(MSG_BURN, value)]
+            }
+            TokenFactoryMsgOptions::ForceTransfer {
+                denom,
+                amount,
+                from_address,
+                to_address,
+            } => {
+                let mut value = vec![];
+                encode_protobuf_string(&mut value, 1, sender);
+                encode_protobuf_message(&mut value, 2, &encode_coin(denom, *amount));
+                encode_protobuf_string(&mut value, 3, from_address);
+                encode_protobuf_string(&mut value, 4, to_address);
+                vec![stargate(MSG_FORCE_TRANSFER, value)]
+            }
+            TokenFactoryMsgOptions::SetMetadata { denom, metadata } => {
+                let value = encode_set_denom_metadata(sender, denom, metadata);
+                vec![stargate(MSG_SET_DENOM_METADATA, value)]
+            }
+            TokenFactoryMsgOptions::SetBeforeSendHook {
+                denom,
+                contract_addr,
+            } => {
+                let mut value = vec![];
+                encode_protobuf_string(&mut value, 1, sender);
+                encode_protobuf_string(&mut value, 2, denom);
+                encode_protobuf_string(&mut value, 3, contract_addr);
+                vec![stargate(MSG_SET_BEFORE_SEND_HOOK, value)]
+            }
+        }
+    }
+}
+
+/// cosmos.base.v1beta1.Coin
+fn encode_coin(denom: &str, amount: Uint128) -> Vec<u8> {
+    let mut data = vec![];
+    encode_protobuf_string(&mut data, 1, denom);
+    encode_protobuf_string(&mut data, 2, &amount.to_string());
+    data
+}
+
+/// osmosis.tokenfactory.v1beta1.MsgSetDenomMetadata.
+/// The chain takes the denom from the metadata's base.
+fn encode_set_denom_metadata(sender: &str, denom: &str, metadata: &Metadata) -> Vec<u8> {
+    let mut data = vec![];
+    encode_protobuf_string(&mut data, 1, sender);
+    encode_protobuf_message(&mut data, 2, &encode_metadata(denom, metadata));
+    data
+}
+
+/// cosmos.bank.v1beta1.Metadata, with `denom` as base unless it has another one
+fn encode_metadata(denom: &str, metadata: &Metadata) -> Vec<u8> {
+    let mut data = vec![];
+    let field = |value: &Option<String>| value.clone().unwrap_or_default();
+    encode_protobuf_string(&mut data, 1, &field(&metadata.description));
+    for unit in &metadata.denom_units {
+        encode_protobuf_message(&mut data, 2, &encode_denom_unit(unit));
+    }
+    encode_protobuf_string(&mut data, 3, metadata.base.as_deref().unwrap_or(denom));
+    encode_protobuf_string(&mut data, 4, &field(&metadata.display));
+    encode_protobuf_string(&mut data, 5, &field(&metadata.name));
+    encode_protobuf_string(&mut data, 6, &field(&metadata.symbol));
+    encode_protobuf_string(&mut data, 7, &field(&metadata.uri));
+    encode_protobuf_string(&mut data, 8, &field(&metadata.uri_hash));
+    data
+}
+
+/// cosmos.bank.v1beta1.DenomUnit
+fn encode_denom_unit(unit: &DenomUnit) -> Vec<u8> {
+    let mut data = vec![];
+    encode_protobuf_string(&mut data, 1, &unit.denom);
+    encode_protobuf_uint(&mut data, 2, unit.exponent.into());
+    encode_protobuf_repeated_string(&mut data, 3, &unit.aliases);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::Empty;
+
+    fn encode(msg: TokenFactoryMsgOptions) -> Vec<(String, Vec<u8>)> {
+        msg.to_stargate_msgs::<Empty>("s1")
+            .into_iter()
+            .map(|msg| match msg {
+                CosmosMsg::Stargate { type_url, value } => (type_url, value.to_vec()),
+                _ => panic!("not a stargate message"),
+            })
+            .collect()
+    }
+
+    fn bytes(parts: &[&[u8]]) -> Vec<u8> {
+        parts.concat()
+    }
+
+    #[test]
+    fn encodes_every_message() {
+        let cases = [
+            (
+                TokenFactoryMsgOptions::CreateDenom {
+                    subdenom: "fundz".to_string(),
+                    metadata: None,
+                },
+                "/osmosis.tokenfactory.v1beta1.MsgCreateDenom",
+                bytes(&[b"\x0a\x02s1", b"\x12\x05fundz"]),
+            ),
+            (
+                TokenFactoryMsgOptions::ChangeAdmin {
+                    denom: "d".to_string(),
+                    new_admin_address: "a".to_string(),
+                },
+                "/osmosis.tokenfactory.v1beta1.MsgChangeAdmin",
+                bytes(&[b"\x0a\x02s1", b"\x12\x01d", b"\x1a\x01a"]),
+            ),
+            (
+                TokenFactoryMsgOptions::MintTokens {
+                    denom: "d".to_string(),
+                    amount: Uint128::new(100),
+                    mint_to_address: "r".to_string(),
+                },
+                "/osmosis.tokenfactory.v1beta1.MsgMint",
+                bytes(&[b"\x0a\x02s1", b"\x12\x08\x0a\x01d\x12\x03100", b"\x1a\x01r"]),
+            ),
+            (
+                TokenFactoryMsgOptions::BurnTokens {
+                    denom: "d".to_string(),
+                    amount: Uint128::new(100),
+                    burn_from_address: "r".to_string(),
+                },
+                "/osmosis.tokenfactory.v1beta1.MsgBurn",
+                bytes(&[b"\x0a\x02s1", b"\x12\x08\x0a\x01d\x12\x03100", b"\x1a\x01r"]),
+            ),
+            (
+                TokenFactoryMsgOptions::ForceTransfer {
+                    denom: "d".to_string(),
+                    amount: Uint128::new(100),
+                    from_address: "f".to_string(),
+                    to_address: "t".to_string(),
+                },
+                "/osmosis.tokenfactory.v1beta1.MsgForceTransfer",
+                bytes(&[
+                    b"\x0a\x02s1",
+                    b"\x12\x08\x0a\x01d\x12\x03100",
+                    b"\x1a\x01f",
+                    b"\x22\x01t",
+                ]),
+            ),
+            (
+                TokenFactoryMsgOptions::SetMetadata {
+                    denom: "d".to_string(),
+                    metadata: Metadata {
+                        description: None,
+                        denom_units: vec![DenomUnit {
+                            denom: "u".to_string(),
+                            exponent: 6,
+                            aliases: vec!["a".to_string()],
+                        }],
+                        base: Some("d".to_string()),
+                        display: None,
+                        name: Some("n".to_string()),
+                        symbol: None,
+                        uri: None,
+                        uri_hash: Some("h".to_string()),
+                    },
+                },
+                "/osmosis.tokenfactory.v1beta1.MsgSetDenomMetadata",
+                bytes(&[
+                    b"\x0a\x02s1",
+                    b"\x12\x13",
+                    b"\x12\x08\x0a\x01u\x10\x06\x1a\x01a",
+                    b"\x1a\x01d",
+                    b"\x2a\x01n",
+                    b"\x42\x01h",
+                ]),
+            ),
+            (
+                TokenFactoryMsgOptions::SetBeforeSendHook {
+                    denom: "d".to_string(),
+                    contract_addr: "c".to_string(),
+                },
+                "/osmosis.tokenfactory.v1beta1.MsgSetBeforeSendHook",
+                bytes(&[b"\x0a\x02s1", b"\x12\x01d", b"\x1a\x01c"]),
+            ),
+        ];
+        for (msg, type_url, value) in cases {
+            assert_eq!(
+                encode(msg.clone()),
+                vec![(type_url.to_string(), value)],
+                "{:?}",
+                msg
+            );
+        }
+    }
+
+    #[test]
+    fn create_denom_with_metadata() {
+        let metadata = Metadata::builder("factory/s1/fundz").build().unwrap();
+        let msgs = encode(TokenFactoryMsgOptions::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: Some(metadata.clone()),
+        });
+        let set_metadata = encode(TokenFactoryMsgOptions::SetMetadata {
+            denom: "factory/s1/fundz".to_string(),
+            metadata,
+        });
+        assert_eq!(msgs.len(), 2);
+        assert_eq!(msgs[0].0, "/osmosis.tokenfactory.v1beta1.MsgCreateDenom");
+        assert_eq!(msgs[1], set_metadata[0]);
+    }
+
+    #[test]
+    fn custom_package() {
+        let msg = TokenFactoryMsg::change_admin("d", String::new());
+        let msgs =
+            msg.to_stargate_msgs_with_package::<Empty>("s1", "cosmwasm.tokenfactory.v1beta1");
+        assert_eq!(
+            msgs,
+            vec![CosmosMsg::Stargate {
+                type_url: "/cosmwasm.tokenfactory.v1beta1.MsgChangeAdmin".to_string(),
+                // the empty admin is omitted
+                value: Binary::from(b"\x0a\x02s1\x12\x01d".to_vec()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Coin;
+
+/// This is sent to the `sudo` entry point of the contract registered
+/// via `TokenFactoryMsgOptions::SetBeforeSendHook` before every bank transfer
+/// of the hooked denom (including mints, burns and force transfers).
+#[cw_serde]
+pub enum BeforeSendHookSudoMsg {
+    /// Returning an error aborts the transfer.
+    /// Runs with a limited gas budget.
+    BlockBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
+    /// Allows the hook to record the transfer.
+    /// Errors are ignored and do not abort the transfer.
+    TrackBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{coin, to_json_string};
+
+    #[test]
+    fn sudo_msg_json_matches_chain() {
+        let msg = BeforeSendHookSudoMsg::BlockBeforeSend {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: coin(100, "factory/creator/fundz"),
+        };
+        assert_eq!(
+            to_json_string(&msg).unwrap(),
+            r#"{"block_before_send":{"from":"alice","to":"bob","amount":{"denom":"factory/creator/fundz","amount":"100"}}}"#
+        );
+    }
+}
@@ -1,5 +1,10 @@
+use std::collections::HashSet;
+
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Coin, StdError};
+use thiserror::Error;
+
+use crate::denom::{validate_native_denom, DenomError};
 
 /// This maps to cosmos.bank.v1beta1.Metadata protobuf struct
 #[cw_serde]
@@ -16,6 +21,291 @@ pub struct Metadata {
     /// symbol is the token symbol usually shown on exchanges (eg: ATOM). This can
     /// be the same as the display.
     pub symbol: Option<String>,
+    /// uri to a document (on or off-chain) that contains additional information,
+    /// e.g. a logo. Missing in JSON from chains that don't support it yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// uri_hash is the hex encoded SHA-256 hash of the document uri points to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri_hash: Option<String>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MetadataError {
+    #[error("{0}")]
+    Denom(#[from] DenomError),
+
+    #[error("name field cannot be blank")]
+    BlankName,
+
+    #[error("symbol field cannot be blank")]
+    BlankSymbol,
+
+    #[error("base denom cannot be blank")]
+    BlankBase,
+
+    #[error("display denom cannot be blank")]
+    BlankDisplay,
+
+    #[error("metadata's base '{base}' must be the denom '{denom}'")]
+    BaseMismatch { base: String, denom: String },
+
+    #[error("metadata's first denomination unit must be the one with base denom '{base}'")]
+    FirstUnitNotBase { base: String },
+
+    #[error("the exponent for base denomination unit {denom} must be 0")]
+    BaseExponentNotZero { denom: String },
+
+    #[error("the denomination units must be sorted in ascending order of exponents")]
+    UnsortedUnits,
+
+    #[error("duplicate denomination unit {denom}")]
+    DuplicateUnit { denom: String },
+
+    #[error("alias field cannot be blank")]
+    BlankAlias,
+
+    #[error("duplicate denomination unit alias {alias}")]
+    DuplicateAlias { alias: String },
+
+    #[error("metadata's display '{display}' does not exist in the denomination units")]
+    DisplayNotInUnits { display: String },
+
+    #[error("alias of denomination unit {denom}, which does not exist")]
+    AliasOfMissingUnit { denom: String },
+}
+
+impl From<MetadataError> for StdError {
+    fn from(err: MetadataError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
+
+impl Metadata {
+    /// Checks the metadata may be set for `denom`, applying the rules
+    /// of the bank module (Metadata.Validate) and requiring `denom` as base.
+    pub fn validate(&self, denom: &str) -> Result<(), MetadataError> {
+        if is_blank(&self.name) {
+            return Err(MetadataError::BlankName);
+        }
+        if is_blank(&self.symbol) {
+            return Err(MetadataError::BlankSymbol);
+        }
+        let base = self.base.as_deref().ok_or(MetadataError::BlankBase)?;
+        let display = self.display.as_deref().ok_or(MetadataError::BlankDisplay)?;
+        validate_native_denom(base)?;
+        validate_native_denom(display)?;
+        if base != denom {
+            return Err(MetadataError::BaseMismatch {
+                base: base.to_string(),
+                denom: denom.to_string(),
+            });
+        }
+
+        match self.denom_units.first() {
+            Some(unit) if unit.denom == base => {}
+            _ => {
+                return Err(MetadataError::FirstUnitNotBase {
+                    base: base.to_string(),
+                })
+            }
+        }
+        let mut seen = HashSet::new();
+        let mut has_display = false;
+        let mut last_exponent = None;
+        for unit in &self.denom_units {
+            match last_exponent {
+                None if unit.exponent != 0 => {
+                    return Err(MetadataError::BaseExponentNotZero {
+                        denom: unit.denom.clone(),
+                    })
+                }
+                Some(last) if unit.exponent <= last => return Err(MetadataError::UnsortedUnits),
+                _ => {}
+            }
+            last_exponent = Some(unit.exponent);
+
+            if !seen.insert(unit.denom.as_str()) {
+                return Err(MetadataError::DuplicateUnit {
+                    denom: unit.denom.clone(),
+                });
+            }
+            has_display |= unit.denom == display;
+            unit.validate()?;
+        }
+        if !has_display {
+            return Err(MetadataError::DisplayNotInUnits {
+                display: display.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Starts building metadata for the `base` denom, see `MetadataBuilder`
+    pub fn builder(base: impl Into<String>) -> MetadataBuilder {
+        MetadataBuilder::new(base)
+    }
+
+    /// Returns the unit with this denom or alias
+    pub fn unit_by_denom(&self, denom: &str) -> Option<&DenomUnit> {
+        self.denom_units
+            .iter()
+            .find(|unit| unit.denom == denom || unit.aliases.iter().any(|alias| alias == denom))
+    }
+
+    /// Returns the unit clients should display amounts in
+    pub fn display_unit(&self) -> Option<&DenomUnit> {
+        self.unit_by_denom(self.display.as_deref()?)
+    }
+
+    /// Returns the number of decimals of the display unit,
+    /// i.e. 6 if 1 display unit is 10^6 base units
+    pub fn decimals(&self) -> Option<u32> {
+        self.display_unit().map(|unit| unit.exponent)
+    }
+}
+
+/// Builds metadata that passes `Metadata::validate` for its base denom.
+///
+/// ```
+/// use token_bindings::Metadata;
+///
+/// let metadata = Metadata::builder("factory/osmo1creator/fundz")
+///     .display("FUNDZ", 6)
+///     .name("Fundz")
+///     .symbol("FUNDZ")
+///     .build()
+///     .unwrap();
+/// assert_eq!(metadata.decimals(), Some(6));
+/// ```
+///
+/// The base unit (exponent 0) is always included. The display unit defaults to it,
+/// the symbol to the display denom and the name to the symbol.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetadataBuilder {
+    base: String,
+    units: Vec<DenomUnit>,
+    display: Option<String>,
+    description: Option<String>,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+    uri_hash: Option<String>,
+    // the first denom `alias` didn't find a unit for, reported by `build`
+    missing_alias_unit: Option<String>,
+}
+
+impl MetadataBuilder {
+    pub fn new(base: impl Into<String>) -> Self {
+        let base: String = base.into();
+        MetadataBuilder {
+            units: vec![DenomUnit {
+                denom: base.clone(),
+                exponent: 0,
+                aliases: vec![],
+            }],
+            base,
+            display: None,
+            description: None,
+            name: None,
+            symbol: None,
+            uri: None,
+            uri_hash: None,
+            missing_alias_unit: None,
+        }
+    }
+
+    /// Adds a unit worth 10^exponent base units
+    pub fn unit(mut self, denom: impl Into<String>, exponent: u32) -> Self {
+        self.units.push(DenomUnit {
+            denom: denom.into(),
+            exponent,
+            aliases: vec![],
+        });
+        self
+    }
+
+    /// Adds an alias to the unit with the given denom, which must have been added before.
+    /// Otherwise `build` fails.
+    pub fn alias(mut self, denom: &str, alias: impl Into<String>) -> Self {
+        match self.units.iter_mut().find(|unit| unit.denom == denom) {
+            Some(unit) => unit.aliases.push(alias.into()),
+            None => {
+                self.missing_alias_unit
+                    .get_or_insert_with(|| denom.to_string());
+            }
+        }
+        self
+    }
+
+    /// Adds a unit worth 10^exponent base units and displays amounts in it
+    pub fn display(self, denom: impl Into<String>, exponent: u32) -> Self {
+        let denom: String = denom.into();
+        let mut builder = self.unit(denom.clone(), exponent);
+        builder.display = Some(denom);
+        builder
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Points to a document with more information, along with its hex encoded SHA-256 hash
+    pub fn uri(mut self, uri: impl Into<String>, uri_hash: impl Into<String>) -> Self {
+        self.uri = Some(uri.into());
+        self.uri_hash = Some(uri_hash.into());
+        self
+    }
+
+    /// Sorts the units by exponent and validates the result
+    pub fn build(self) -> Result<Metadata, MetadataError> {
+        let MetadataBuilder {
+            base,
+            mut units,
+            display,
+            description,
+            name,
+            symbol,
+            uri,
+            uri_hash,
+            missing_alias_unit,
+        } = self;
+        if let Some(denom) = missing_alias_unit {
+            return Err(MetadataError::AliasOfMissingUnit { denom });
+        }
+        units.sort_by_key(|unit| unit.exponent);
+        let display = display.unwrap_or_else(|| base.clone());
+        let symbol = symbol.unwrap_or_else(|| display.clone());
+        let name = name.unwrap_or_else(|| symbol.clone());
+
+        let metadata = Metadata {
+            description,
+            denom_units: units,
+            base: Some(base.clone()),
+            display: Some(display),
+            name: Some(name),
+            symbol: Some(symbol),
+            uri,
+            uri_hash,
+        };
+        metadata.validate(&base)?;
+        Ok(metadata)
+    }
+}
+
+fn is_blank(field: &Option<String>) -> bool {
+    field.as_deref().unwrap_or_default().trim().is_empty()
 }
 
 /// This maps to cosmos.bank.v1beta1.DenomUnit protobuf struct
@@ -33,9 +323,237 @@ pub struct DenomUnit {
     pub aliases: Vec<String>,
 }
 
+impl DenomUnit {
+    /// Checks the rules of the bank module (DenomUnit.Validate)
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        validate_native_denom(&self.denom)?;
+        let mut seen = HashSet::new();
+        for alias in &self.aliases {
+            if alias.trim().is_empty() {
+                return Err(MetadataError::BlankAlias);
+            }
+            if !seen.insert(alias.as_str()) {
+                return Err(MetadataError::DuplicateAlias {
+                    alias: alias.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
 /// This maps to osmosis.tokenfactory.v1beta1.Params protobuf struct
 #[cw_serde]
+#[derive(Default)]
 pub struct Params {
-    /// TODO: verify semantics - does it charge all of these or one of these?
+    /// Every one of these coins is charged from the creator of a new denom
     pub denom_creation_fee: Vec<Coin>,
+    /// Gas consumed when creating a denom, on top of the gas of the message itself.
+    /// Zero on chains that don't support it, which leave it out of the JSON.
+    #[serde(default)]
+    pub denom_creation_gas_consume: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DENOM: &str = "factory/cosmos1creator/fundz";
+
+    fn unit(denom: &str, exponent: u32, aliases: &[&str]) -> DenomUnit {
+        DenomUnit {
+            denom: denom.to_string(),
+            exponent,
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    fn valid_metadata() -> Metadata {
+        Metadata {
+            description: None,
+            denom_units: vec![unit(DENOM, 0, &["microfundz"]), unit("FUNDZ", 6, &[])],
+            base: Some(DENOM.to_string()),
+            display: Some("FUNDZ".to_string()),
+            name: Some("Fundz".to_string()),
+            symbol: Some("FUNDZ".to_string()),
+            uri: None,
+            uri_hash: None,
+        }
+    }
+
+    #[test]
+    fn builder() {
+        let metadata = Metadata::builder(DENOM)
+            .alias(DENOM, "microfundz")
+            .display("FUNDZ", 6)
+            .name("Fundz")
+            .symbol("FUNDZ")
+            .build()
+            .unwrap();
+        assert_eq!(metadata, valid_metadata());
+
+        // units are sorted and defaults filled in
+        let metadata = Metadata::builder(DENOM)
+            .display("FUNDZ", 6)
+            .unit("MILLIFUNDZ", 3)
+            .description("Fundz pay")
+            .build()
+            .unwrap();
+        let exponents: Vec<u32> = metadata.denom_units.iter().map(|u| u.exponent).collect();
+        assert_eq!(exponents, vec![0, 3, 6]);
+        assert_eq!(metadata.symbol.as_deref(), Some("FUNDZ"));
+        assert_eq!(metadata.name.as_deref(), Some("FUNDZ"));
+        assert_eq!(metadata.description.as_deref(), Some("Fundz pay"));
+
+        let metadata = Metadata::builder(DENOM).build().unwrap();
+        assert_eq!(metadata.display.as_deref(), Some(DENOM));
+        assert_eq!(metadata.decimals(), Some(0));
+
+        // invalid metadata is not built
+        let err = Metadata::builder(DENOM)
+            .display("FUNDZ", 6)
+            .unit("FUNDZ", 9)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            MetadataError::DuplicateUnit {
+                denom: "FUNDZ".to_string()
+            }
+        );
+        let err = Metadata::builder("1fundz").build().unwrap_err();
+        assert!(matches!(err, MetadataError::Denom(_)));
+
+        // aliases need their unit first
+        let err = Metadata::builder(DENOM)
+            .alias("FUNDZ", "fundies")
+            .display("FUNDZ", 6)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            MetadataError::AliasOfMissingUnit {
+                denom: "FUNDZ".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn uri_fields_are_optional() {
+        // metadata from chains without uri support
+        let json = br#"{"description":null,"denom_units":[],"base":"uosmo","display":null,"name":null,"symbol":null}"#;
+        let metadata: Metadata = cosmwasm_std::from_json(json).unwrap();
+        assert_eq!(metadata.uri, None);
+        assert_eq!(metadata.uri_hash, None);
+        // and they are not sent to them unless set
+        assert_eq!(cosmwasm_std::to_json_vec(&metadata).unwrap(), json.to_vec());
+
+        let metadata = Metadata::builder(DENOM)
+            .uri("https://fundz.example/logo.png", "ab".repeat(32))
+            .build()
+            .unwrap();
+        let json = cosmwasm_std::to_json_string(&metadata).unwrap();
+        assert!(json.contains(r#""uri":"https://fundz.example/logo.png""#));
+        assert_eq!(cosmwasm_std::from_json::<Metadata>(json).unwrap(), metadata);
+    }
+
+    #[test]
+    fn params_without_gas_consume() {
+        let json = br#"{"denom_creation_fee":[{"denom":"uosmo","amount":"100"}]}"#;
+        let params: Params = cosmwasm_std::from_json(json).unwrap();
+        assert_eq!(
+            params,
+            Params {
+                denom_creation_fee: cosmwasm_std::coins(100, "uosmo"),
+                denom_creation_gas_consume: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn unit_lookups() {
+        let metadata = valid_metadata();
+        assert_eq!(metadata.unit_by_denom("FUNDZ").unwrap().exponent, 6);
+        assert_eq!(metadata.unit_by_denom("microfundz").unwrap().denom, DENOM);
+        assert_eq!(metadata.unit_by_denom("KILOFUNDZ"), None);
+        assert_eq!(metadata.display_unit().unwrap().denom, "FUNDZ");
+        assert_eq!(metadata.decimals(), Some(6));
+
+        let no_display = Metadata {
+            display: None,
+            ..metadata
+        };
+        assert_eq!(no_display.display_unit(), None);
+        assert_eq!(no_display.decimals(), None);
+    }
+
+    #[test]
+    fn validate_metadata() {
+        valid_metadata().validate(DENOM).unwrap();
+
+        type Modify = fn(&mut Metadata);
+        let cases: Vec<(Modify, MetadataError)> = vec![
+            (|m| m.name = None, MetadataError::BlankName),
+            (|m| m.name = Some(" ".to_string()), MetadataError::BlankName),
+            (|m| m.symbol = None, MetadataError::BlankSymbol),
+            (|m| m.base = None, MetadataError::BlankBase),
+            (|m| m.display = None, MetadataError::BlankDisplay),
+            (
+                |m| m.display = Some("1FUNDZ".to_string()),
+                MetadataError::Denom(DenomError::InvalidDenom {
+                    denom: "1FUNDZ".to_string(),
+                }),
+            ),
+            (
+                |m| m.base = Some("uosmo".to_string()),
+                MetadataError::BaseMismatch {
+                    base: "uosmo".to_string(),
+                    denom: DENOM.to_string(),
+                },
+            ),
+            (
+                |m| m.denom_units.reverse(),
+                MetadataError::FirstUnitNotBase {
+                    base: DENOM.to_string(),
+                },
+            ),
+            (
+                |m| m.denom_units[0].exponent = 1,
+                MetadataError::BaseExponentNotZero {
+                    denom: DENOM.to_string(),
+                },
+            ),
+            (
+                |m| m.denom_units[1].exponent = 0,
+                MetadataError::UnsortedUnits,
+            ),
+            (
+                |m| m.denom_units.push(unit("FUNDZ", 9, &[])),
+                MetadataError::DuplicateUnit {
+                    denom: "FUNDZ".to_string(),
+                },
+            ),
+            (
+                |m| m.denom_units[1].aliases = vec!["kilo".to_string(), "kilo".to_string()],
+                MetadataError::DuplicateAlias {
+                    alias: "kilo".to_string(),
+                },
+            ),
+            (
+                |m| m.denom_units[1].aliases = vec!["".to_string()],
+                MetadataError::BlankAlias,
+            ),
+            (
+                |m| m.display = Some("MEGAFUNDZ".to_string()),
+                MetadataError::DisplayNotInUnits {
+                    display: "MEGAFUNDZ".to_string(),
+                },
+            ),
+        ];
+        for (modify, expected) in cases {
+            let mut metadata = valid_metadata();
+            modify(&mut metadata);
+            assert_eq!(metadata.validate(DENOM).unwrap_err(), expected);
+        }
+    }
 }
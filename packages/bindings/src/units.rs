@@ -0,0 +1,331 @@
+use cosmwasm_std::{Decimal, Decimal256, StdError, Uint128, Uint256, Uint512};
+use thiserror::Error;
+
+use crate::Metadata;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ConversionError {
+    #[error("metadata has no unit '{denom}'")]
+    UnknownUnit { denom: String },
+
+    #[error("conversion would lose precision")]
+    PrecisionLoss,
+
+    #[error("conversion overflow")]
+    Overflow,
+}
+
+impl From<ConversionError> for StdError {
+    fn from(err: ConversionError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
+
+impl Metadata {
+    /// Converts an integer amount of the `from` unit into the `to` unit.
+    /// E.g. 1_500_000 of the base unit are 1500 of a unit with exponent 3,
+    /// but converting them to a unit with exponent 6 fails with PrecisionLoss.
+    pub fn convert(
+        &self,
+        amount: Uint128,
+        from: &str,
+        to: &str,
+    ) -> Result<Uint128, ConversionError> {
+        let atomics = self.convert_atomics(amount.into(), 0, from, to, 0)?;
+        Uint128::try_from(atomics).map_err(|_| ConversionError::Overflow)
+    }
+
+    /// Converts an integer amount of the `from` unit into a decimal amount of the `to` unit
+    pub fn convert_to_decimal(
+        &self,
+        amount: Uint128,
+        from: &str,
+        to: &str,
+    ) -> Result<Decimal, ConversionError> {
+        let atomics = self.convert_atomics(amount.into(), 0, from, to, Decimal::DECIMAL_PLACES)?;
+        let atomics = Uint128::try_from(atomics).map_err(|_| ConversionError::Overflow)?;
+        Ok(Decimal::new(atomics))
+    }
+
+    /// Converts an integer amount of the `from` unit into a decimal amount of the `to` unit
+    pub fn convert_to_decimal256(
+        &self,
+        amount: Uint128,
+        from: &str,
+        to: &str,
+    ) -> Result<Decimal256, ConversionError> {
+        let atomics =
+            self.convert_atomics(amount.into(), 0, from, to, Decimal256::DECIMAL_PLACES)?;
+        let atomics = Uint256::try_from(atomics).map_err(|_| ConversionError::Overflow)?;
+        Ok(Decimal256::new(atomics))
+    }
+
+    /// Converts a decimal amount of the `from` unit into an integer amount of the `to` unit
+    pub fn convert_from_decimal(
+        &self,
+        amount: Decimal,
+        from: &str,
+        to: &str,
+    ) -> Result<Uint128, ConversionError> {
+        let atomics = self.convert_atomics(
+            amount.atomics().into(),
+            Decimal::DECIMAL_PLACES,
+            from,
+            to,
+            0,
+        )?;
+        Uint128::try_from(atomics).map_err(|_| ConversionError::Overflow)
+    }
+
+    /// Converts a decimal amount of the `from` unit into an integer amount of the `to` unit
+    pub fn convert_from_decimal256(
+        &self,
+        amount: Decimal256,
+        from: &str,
+        to: &str,
+    ) -> Result<Uint128, ConversionError> {
+        let atomics = self.convert_atomics(
+            amount.atomics().into(),
+            Decimal256::DECIMAL_PLACES,
+            from,
+            to,
+            0,
+        )?;
+        Uint128::try_from(atomics).map_err(|_| ConversionError::Overflow)
+    }
+
+    /// Converts a base amount into the display unit, e.g. for showing it to users
+    pub fn to_display(&self, amount: Uint128) -> Result<Decimal, ConversionError> {
+        let (base, display) = self.base_and_display()?;
+        self.convert_to_decimal(amount, base, display)
+    }
+
+    /// Converts an amount of the display unit into the base unit, e.g. for minting it
+    pub fn from_display(&self, amount: Decimal) -> Result<Uint128, ConversionError> {
+        let (base, display) = self.base_and_display()?;
+        self.convert_from_decimal(amount, display, base)
+    }
+
+    fn base_and_display(&self) -> Result<(&str, &str), ConversionError> {
+        let unknown = |denom: &Option<String>| ConversionError::UnknownUnit {
+            denom: denom.clone().unwrap_or_default(),
+        };
+        let base = self.base.as_deref().ok_or_else(|| unknown(&self.base))?;
+        let display = self
+            .display
+            .as_deref()
+            .ok_or_else(|| unknown(&self.display))?;
+        Ok((base, display))
+    }
+
+    fn exponent(&self, denom: &str) -> Result<u32, ConversionError> {
+        self.unit_by_denom(denom)
+            .map(|unit| unit.exponent)
+            .ok_or_else(|| ConversionError::UnknownUnit {
+                denom: denom.to_string(),
+            })
+    }
+
+    /// Rescales the atomics of an amount of `from` with `decimal_places`
+    /// (0 for integers, 18 for decimals) to atomics of `to` with `to_decimal_places`.
+    /// Fails unless the result is exact.
+    fn convert_atomics(
+        &self,
+        value: Uint512,
+        decimal_places: u32,
+        from: &str,
+        to: &str,
+        to_decimal_places: u32,
+    ) -> Result<Uint512, ConversionError> {
+        // value * 10^(from + to_decimal_places) / 10^(to + decimal_places)
+        let up = i64::from(self.exponent(from)?) + i64::from(to_decimal_places);
+        let down = i64::from(self.exponent(to)?) + i64::from(decimal_places);
+        if value.is_zero() {
+            return Ok(Uint512::zero());
+        }
+        if up >= down {
+            let factor = pow10(up - down).ok_or(ConversionError::Overflow)?;
+            value
+                .checked_mul(factor)
+                .map_err(|_| ConversionError::Overflow)
+        } else {
+            // a divisor too large to represent leaves a remainder for any non-zero value
+            let divisor = pow10(down - up).ok_or(ConversionError::PrecisionLoss)?;
+            if !(value % divisor).is_zero() {
+                return Err(ConversionError::PrecisionLoss);
+            }
+            Ok(value / divisor)
+        }
+    }
+}
+
+fn pow10(exponent: i64) -> Option<Uint512> {
+    let exponent = u32::try_from(exponent).ok()?;
+    Uint512::from(10u32).checked_pow(exponent).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::str::FromStr;
+
+    const DENOM: &str = "factory/cosmos1creator/fundz";
+
+    fn fundz() -> Metadata {
+        Metadata::builder(DENOM)
+            .unit("mfundz", 3)
+            .display("FUNDZ", 6)
+            .unit("GIGAFUNDZ", 15)
+            .unit("HUGEFUNDZ", 60)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn convert_integers() {
+        let metadata = fundz();
+        let amount = Uint128::new(1_500_000);
+        assert_eq!(
+            metadata.convert(amount, DENOM, "mfundz").unwrap(),
+            Uint128::new(1500)
+        );
+        assert_eq!(metadata.convert(amount, DENOM, DENOM).unwrap(), amount);
+        assert_eq!(
+            metadata.convert(Uint128::new(2), "FUNDZ", DENOM).unwrap(),
+            Uint128::new(2_000_000)
+        );
+        assert_eq!(
+            metadata.convert(amount, DENOM, "FUNDZ").unwrap_err(),
+            ConversionError::PrecisionLoss
+        );
+        assert_eq!(
+            metadata.convert(Uint128::MAX, "FUNDZ", DENOM).unwrap_err(),
+            ConversionError::Overflow
+        );
+        assert_eq!(
+            metadata.convert(amount, DENOM, "KILOFUNDZ").unwrap_err(),
+            ConversionError::UnknownUnit {
+                denom: "KILOFUNDZ".to_string()
+            }
+        );
+        // nothing to lose for zero, no matter how large the unit
+        assert_eq!(
+            metadata
+                .convert(Uint128::zero(), DENOM, "HUGEFUNDZ")
+                .unwrap(),
+            Uint128::zero()
+        );
+        assert_eq!(
+            metadata
+                .convert(Uint128::one(), DENOM, "HUGEFUNDZ")
+                .unwrap_err(),
+            ConversionError::PrecisionLoss
+        );
+    }
+
+    #[test]
+    fn convert_decimals() {
+        let metadata = fundz();
+        assert_eq!(
+            metadata.to_display(Uint128::new(1_500_000)).unwrap(),
+            Decimal::from_str("1.5").unwrap()
+        );
+        assert_eq!(
+            metadata
+                .from_display(Decimal::from_str("0.000001").unwrap())
+                .unwrap(),
+            Uint128::one()
+        );
+        assert_eq!(
+            metadata
+                .from_display(Decimal::from_str("0.0000015").unwrap())
+                .unwrap_err(),
+            ConversionError::PrecisionLoss
+        );
+        assert_eq!(
+            metadata
+                .convert_to_decimal256(Uint128::new(1), DENOM, "GIGAFUNDZ")
+                .unwrap(),
+            Decimal256::from_str("0.000000000000001").unwrap()
+        );
+        assert_eq!(
+            metadata
+                .convert_from_decimal256(Decimal256::from_str("2.5").unwrap(), "mfundz", DENOM)
+                .unwrap(),
+            Uint128::new(2500)
+        );
+        // 10^-19 FUNDZ cannot be represented with 18 decimal places
+        assert_eq!(
+            metadata
+                .convert_to_decimal(Uint128::new(1), DENOM, "HUGEFUNDZ")
+                .unwrap_err(),
+            ConversionError::PrecisionLoss
+        );
+        assert_eq!(
+            metadata
+                .convert_to_decimal(Uint128::MAX, "GIGAFUNDZ", DENOM)
+                .unwrap_err(),
+            ConversionError::Overflow
+        );
+        let no_display = Metadata {
+            display: None,
+            ..metadata
+        };
+        assert_eq!(
+            no_display.to_display(Uint128::one()).unwrap_err(),
+            ConversionError::UnknownUnit {
+                denom: String::new()
+            }
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn integer_round_trip(amount in any::<u128>(), to in prop::sample::select(vec![DENOM, "mfundz", "FUNDZ", "GIGAFUNDZ"])) {
+            let metadata = fundz();
+            let amount = Uint128::new(amount);
+            // going up in units loses precision, going down may overflow, but never silently
+            if let Ok(converted) = metadata.convert(amount, DENOM, to) {
+                prop_assert_eq!(metadata.convert(converted, to, DENOM).unwrap(), amount);
+            }
+        }
+
+        #[test]
+        fn integer_units_are_exact(amount in any::<u64>(), exponent in 0u32..=15) {
+            let metadata = fundz();
+            let to = metadata.denom_units.iter().rev().find(|unit| unit.exponent <= exponent).unwrap();
+            let base = Uint128::from(amount) * Uint128::new(10u128.pow(to.exponent));
+            prop_assert_eq!(metadata.convert(base, DENOM, &to.denom).unwrap(), Uint128::from(amount));
+        }
+
+        #[test]
+        fn decimal_round_trip(amount in any::<u128>()) {
+            let metadata = fundz();
+            let amount = Uint128::new(amount);
+            match metadata.to_display(amount) {
+                Ok(display) => prop_assert_eq!(metadata.from_display(display).unwrap(), amount),
+                Err(err) => prop_assert_eq!(err, ConversionError::Overflow),
+            }
+            let display = metadata.convert_to_decimal256(amount, DENOM, "FUNDZ").unwrap();
+            prop_assert_eq!(
+                metadata.convert_from_decimal256(display, "FUNDZ", DENOM).unwrap(),
+                amount
+            );
+        }
+
+        #[test]
+        fn decimal_to_base_round_trip(atomics in any::<u128>()) {
+            let metadata = fundz();
+            let display = Decimal::new(Uint128::new(atomics));
+            match metadata.from_display(display) {
+                Ok(base) => prop_assert_eq!(metadata.to_display(base).unwrap(), display),
+                // only whole base units convert
+                Err(err) => {
+                    prop_assert_eq!(err, ConversionError::PrecisionLoss);
+                    prop_assert!(atomics % 1_000_000_000_000 != 0);
+                }
+            }
+        }
+    }
+}